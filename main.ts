@@ -1,4 +1,20 @@
 import init, { start } from "./pkg/wasm_webgpu_test";
 
-await init();
-start(document.getElementById("GameView"));
+const canvas = document.getElementById("GameView") as HTMLCanvasElement;
+
+if (new URLSearchParams(location.search).has("worker")) {
+    const offscreen = canvas.transferControlToOffscreen();
+    const worker = new Worker(new URL("./worker.ts", import.meta.url), { type: "module" });
+    worker.postMessage({ canvas: offscreen }, [offscreen]);
+
+    new ResizeObserver(([entry]) => {
+        const size = entry.devicePixelContentBoxSize?.[0];
+        worker.postMessage({
+            width: size?.inlineSize ?? Math.round(entry.contentRect.width * devicePixelRatio),
+            height: size?.blockSize ?? Math.round(entry.contentRect.height * devicePixelRatio),
+        });
+    }).observe(canvas);
+} else {
+    await init();
+    await start(canvas);
+}
@@ -1,12 +1,244 @@
-use js_sys::{Array, ArrayBuffer, Float32Array, Object, Reflect};
-use wasm_bindgen::{convert::IntoWasmAbi, prelude::wasm_bindgen, JsCast, JsValue};
+use js_sys::{Array, ArrayBuffer, Object, Reflect};
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeSet,
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::{
+    convert::{FromWasmAbi, IntoWasmAbi},
+    prelude::{wasm_bindgen, Closure},
+    JsCast, JsValue,
+};
 
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
     fn log(s: &str);
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &js_sys::Function, timeout: i32) -> i32;
 }
 
+async fn sleep(milliseconds: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        set_timeout(&resolve, milliseconds);
+    });
+    // the promise never rejects
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GpuError {
+    Validation(String),
+    OutOfMemory(String),
+    Internal(String),
+    DeviceLost {
+        reason: String,
+        message: String,
+    },
+    JsException {
+        name: String,
+        message: String,
+    },
+    Serialization(String),
+    Unknown(String),
+    AdapterUnavailable,
+    UnsupportedFeature(GPUFeatureName),
+    UnknownLimit(String),
+    UnsupportedLimit {
+        name: String,
+        requested: u64,
+        supported: u64,
+    },
+}
+impl std::fmt::Display for GpuError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Validation(message) => write!(f, "validation error: {message}"),
+            Self::OutOfMemory(message) => write!(f, "out of memory: {message}"),
+            Self::Internal(message) => write!(f, "internal error: {message}"),
+            Self::DeviceLost { reason, message } => {
+                write!(f, "device lost ({reason}): {message}")
+            }
+            Self::JsException { name, message } => write!(f, "{name}: {message}"),
+            Self::Serialization(message) => write!(f, "serialization error: {message}"),
+            Self::Unknown(message) => write!(f, "unknown error: {message}"),
+            Self::AdapterUnavailable => f.write_str("no adapters available"),
+            Self::UnsupportedFeature(feature) => {
+                write!(f, "feature {feature:?} is not supported by the adapter")
+            }
+            Self::UnknownLimit(name) => write!(f, "limit {name} is unknown to the adapter"),
+            Self::UnsupportedLimit {
+                name,
+                requested,
+                supported,
+            } => write!(
+                f,
+                "limit {name} = {requested} is not supported by the adapter (supported: {supported})"
+            ),
+        }
+    }
+}
+impl std::error::Error for GpuError {}
+impl From<JsValue> for GpuError {
+    fn from(value: JsValue) -> Self {
+        if !value.is_object() {
+            return Self::JsException {
+                name: "Error".into(),
+                message: value.as_string().unwrap_or_else(|| format!("{value:?}")),
+            };
+        }
+        let string_property = |target: &JsValue, key: &str| {
+            Reflect::get(target, &key.into())
+                .ok()
+                .and_then(|x| x.as_string())
+        };
+        // Reflect is used instead of Object::constructor, which throws for objects without a
+        // prototype
+        let class_name = Reflect::get(&value, &"constructor".into())
+            .ok()
+            .filter(|c| c.is_function())
+            .and_then(|c| string_property(&c, "name"));
+        let message = string_property(&value, "message").unwrap_or_default();
+
+        match class_name.as_deref() {
+            Some("GPUValidationError") => Self::Validation(message),
+            Some("GPUOutOfMemoryError") => Self::OutOfMemory(message),
+            Some("GPUInternalError") => Self::Internal(message),
+            Some("GPUDeviceLostInfo") => Self::DeviceLost {
+                reason: string_property(&value, "reason").unwrap_or_default(),
+                message,
+            },
+            _ => match string_property(&value, "name").or(class_name) {
+                Some(name) => Self::JsException { name, message },
+                None => Self::Unknown(message),
+            },
+        }
+    }
+}
+impl From<serde_wasm_bindgen::Error> for GpuError {
+    fn from(value: serde_wasm_bindgen::Error) -> Self {
+        Self::Serialization(value.to_string())
+    }
+}
+impl From<serde::de::value::Error> for GpuError {
+    fn from(value: serde::de::value::Error) -> Self {
+        Self::Serialization(value.to_string())
+    }
+}
+impl From<GpuError> for JsValue {
+    fn from(value: GpuError) -> Self {
+        let e = js_sys::Error::new(&value.to_string());
+        if let GpuError::JsException { name, .. } = &value {
+            e.set_name(name);
+        }
+
+        e.into()
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GPUErrorFilter {
+    Validation = "validation",
+    OutOfMemory = "out-of-memory",
+    Internal = "internal",
+}
+
+macro_rules! impl_flags_abi {
+    ($($t: ty),*) => {
+        $(
+            impl From<$t> for u32 {
+                fn from(value: $t) -> Self {
+                    value.bits()
+                }
+            }
+            impl IntoWasmAbi for $t {
+                type Abi = u32;
+
+                fn into_abi(self) -> u32 {
+                    self.bits()
+                }
+            }
+            impl FromWasmAbi for $t {
+                type Abi = u32;
+
+                unsafe fn from_abi(js: u32) -> Self {
+                    Self::from_bits_retain(js)
+                }
+            }
+            impl wasm_bindgen::describe::WasmDescribe for $t {
+                fn describe() {
+                    u32::describe()
+                }
+            }
+        )*
+    };
+}
+
+bitflags::bitflags! {
+    #[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+    #[serde(into = "u32")]
+    pub struct BufferUsages: u32 {
+        const MAP_READ = 0x0001;
+        const MAP_WRITE = 0x0002;
+        const COPY_SRC = 0x0004;
+        const COPY_DST = 0x0008;
+        const INDEX = 0x0010;
+        const VERTEX = 0x0020;
+        const UNIFORM = 0x0040;
+        const STORAGE = 0x0080;
+        const INDIRECT = 0x0100;
+        const QUERY_RESOLVE = 0x0200;
+    }
+
+    #[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+    #[serde(into = "u32")]
+    pub struct TextureUsages: u32 {
+        const COPY_SRC = 0x01;
+        const COPY_DST = 0x02;
+        const TEXTURE_BINDING = 0x04;
+        const STORAGE_BINDING = 0x08;
+        const RENDER_ATTACHMENT = 0x10;
+    }
+
+    #[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+    #[serde(into = "u32")]
+    pub struct ShaderStages: u32 {
+        const VERTEX = 0x1;
+        const FRAGMENT = 0x2;
+        const COMPUTE = 0x4;
+    }
+
+    #[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+    #[serde(into = "u32")]
+    pub struct MapMode: u32 {
+        const READ = 0x1;
+        const WRITE = 0x2;
+    }
+
+    #[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    #[serde(into = "u32")]
+    pub struct ColorWrites: u32 {
+        const RED = 0x1;
+        const GREEN = 0x2;
+        const BLUE = 0x4;
+        const ALPHA = 0x8;
+        const ALL = 0xf;
+    }
+}
+impl Default for ColorWrites {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+impl_flags_abi!(
+    BufferUsages,
+    TextureUsages,
+    ShaderStages,
+    MapMode,
+    ColorWrites
+);
+
 #[wasm_bindgen]
 pub struct GPUBufferCreateDescription {
     #[wasm_bindgen(getter_with_clone)]
@@ -14,7 +246,7 @@ pub struct GPUBufferCreateDescription {
     #[wasm_bindgen(js_name = mappedAtCreation)]
     pub mapped_at_creation: Option<bool>,
     pub size: u32,
-    pub usage: u32,
+    pub usage: BufferUsages,
 }
 
 #[wasm_bindgen]
@@ -29,42 +261,92 @@ pub struct GPUCommandBufferDescriptor {
     pub label: Option<String>,
 }
 
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Default)]
+pub struct GPUColor {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+    pub a: f64,
+}
+impl GPUColor {
+    pub const TRANSPARENT: Self = Self::new(0.0, 0.0, 0.0, 0.0);
+    pub const BLACK: Self = Self::new(0.0, 0.0, 0.0, 1.0);
+    pub const WHITE: Self = Self::new(1.0, 1.0, 1.0, 1.0);
+
+    pub const fn new(r: f64, g: f64, b: f64, a: f64) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub fn into_object(self) -> Result<Object, GpuError> {
+        Ok(serde_wasm_bindgen::to_value(&self)?.unchecked_into())
+    }
+}
+impl From<[f32; 4]> for GPUColor {
+    fn from([r, g, b, a]: [f32; 4]) -> Self {
+        Self::new(r as _, g as _, b as _, a as _)
+    }
+}
+
 #[repr(transparent)]
 pub struct GPURenderPassColorAttachment(Object);
 impl GPURenderPassColorAttachment {
-    pub fn new(view: &GPUTextureView) -> Self {
+    pub fn new(view: &GPUTextureView) -> Result<Self, GpuError> {
         let obj = Object::new();
 
-        Reflect::set(&obj, &"view".into(), &view).unwrap();
-        Reflect::set(&obj, &"loadOp".into(), &"load".into()).unwrap();
-        Reflect::set(&obj, &"storeOp".into(), &"store".into()).unwrap();
+        Reflect::set(&obj, &"view".into(), view)?;
 
         Self(obj)
+            .load_op(GPULoadOp::Load)?
+            .store_op(GPUStoreOp::Store)
     }
 
-    pub fn resolve_to(self, view: &GPUTextureView) -> Self {
-        Reflect::set(&self.0, &"resolveTarget".into(), view).unwrap();
+    pub fn resolve_to(self, view: &GPUTextureView) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"resolveTarget".into(), view)?;
 
-        self
+        Ok(self)
     }
 
-    fn clear_by(self, value: &JsValue) -> Self {
-        Reflect::set(&self.0, &"clearValue".into(), value).unwrap();
-        Reflect::set(&self.0, &"loadOp".into(), &"clear".into()).unwrap();
+    pub fn load_op(self, op: GPULoadOp) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"loadOp".into(),
+            &serde_wasm_bindgen::to_value(&op)?,
+        )?;
 
-        self
+        Ok(self)
     }
 
-    pub fn clear_by_array(self, values: &[f32; 4]) -> Self {
-        let values: Array = values
-            .into_iter()
-            .map(|&x| JsValue::from_f64(x as _))
-            .collect();
+    pub fn store_op(self, op: GPUStoreOp) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"storeOp".into(),
+            &serde_wasm_bindgen::to_value(&op)?,
+        )?;
+
+        Ok(self)
+    }
+
+    pub fn depth_slice(self, slice: u32) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"depthSlice".into(), &slice.into())?;
+
+        Ok(self)
+    }
+
+    fn clear_by(self, value: &JsValue) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"clearValue".into(), value)?;
+
+        self.load_op(GPULoadOp::Clear)
+    }
 
-        self.clear_by(&values)
+    pub fn clear(self, color: GPUColor) -> Result<Self, GpuError> {
+        self.clear_by(&serde_wasm_bindgen::to_value(&color)?)
     }
 
-    pub fn clear_by_object(self, value: &Object) -> Self {
+    pub fn clear_by_array(self, values: &[f32; 4]) -> Result<Self, GpuError> {
+        self.clear(GPUColor::from(*values))
+    }
+
+    pub fn clear_by_object(self, value: &Object) -> Result<Self, GpuError> {
         self.clear_by(value)
     }
 }
@@ -86,11 +368,96 @@ impl wasm_bindgen::describe::WasmDescribe for GPURenderPassColorAttachment {
     }
 }
 
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GPULoadOp {
+    Load,
+    Clear,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GPUStoreOp {
+    Store,
+    Discard,
+}
+
+pub struct GPURenderPassDepthStencilAttachment(Object);
+impl GPURenderPassDepthStencilAttachment {
+    pub fn new(view: &GPUTextureView) -> Result<Self, GpuError> {
+        let obj = Object::new();
+
+        Reflect::set(&obj, &"view".into(), view)?;
+
+        Ok(Self(obj))
+    }
+
+    pub fn depth_ops(self, load_op: GPULoadOp, store_op: GPUStoreOp) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"depthLoadOp".into(),
+            &serde_wasm_bindgen::to_value(&load_op)?,
+        )?;
+        Reflect::set(
+            &self.0,
+            &"depthStoreOp".into(),
+            &serde_wasm_bindgen::to_value(&store_op)?,
+        )?;
+
+        Ok(self)
+    }
+
+    pub fn depth_clear_value(self, value: f32) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"depthClearValue".into(), &value.into())?;
+
+        Ok(self)
+    }
+
+    pub fn depth_read_only(self) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"depthReadOnly".into(), &true.into())?;
+
+        Ok(self)
+    }
+
+    pub fn stencil_ops(self, load_op: GPULoadOp, store_op: GPUStoreOp) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"stencilLoadOp".into(),
+            &serde_wasm_bindgen::to_value(&load_op)?,
+        )?;
+        Reflect::set(
+            &self.0,
+            &"stencilStoreOp".into(),
+            &serde_wasm_bindgen::to_value(&store_op)?,
+        )?;
+
+        Ok(self)
+    }
+
+    pub fn stencil_clear_value(self, value: u32) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"stencilClearValue".into(), &value.into())?;
+
+        Ok(self)
+    }
+
+    pub fn stencil_read_only(self) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"stencilReadOnly".into(), &true.into())?;
+
+        Ok(self)
+    }
+}
+impl AsRef<JsValue> for GPURenderPassDepthStencilAttachment {
+    fn as_ref(&self) -> &JsValue {
+        self.0.as_ref()
+    }
+}
+
 pub struct GPURenderPassDescriptor {
     pub color_attachments: Vec<GPURenderPassColorAttachment>,
+    pub depth_stencil_attachment: Option<GPURenderPassDepthStencilAttachment>,
 }
 impl GPURenderPassDescriptor {
-    pub fn into_object(self) -> Result<Object, JsValue> {
+    pub fn into_object(self) -> Result<Object, GpuError> {
         let obj = Object::new();
 
         Reflect::set(
@@ -98,37 +465,132 @@ impl GPURenderPassDescriptor {
             &"colorAttachments".into(),
             &Array::from_iter(self.color_attachments),
         )?;
+        if let Some(ds) = self.depth_stencil_attachment {
+            Reflect::set(&obj, &"depthStencilAttachment".into(), ds.as_ref())?;
+        }
 
         Ok(obj)
     }
 }
-impl IntoWasmAbi for GPURenderPassDescriptor {
-    type Abi = <Object as IntoWasmAbi>::Abi;
-
-    fn into_abi(self) -> Self::Abi {
-        Object::into_abi(self.into_object().expect("Failed to convert to wasm abi"))
-    }
-}
-impl wasm_bindgen::describe::WasmDescribe for GPURenderPassDescriptor {
-    fn describe() {
-        Object::describe()
-    }
-}
 
 pub struct GPUCanvasConfiguration(Object);
 impl GPUCanvasConfiguration {
-    pub fn new(device: &GPUDevice) -> Self {
+    pub fn new(device: &GPUDevice) -> Result<Self, GpuError> {
         let o = Object::new();
 
-        Reflect::set(&o, &"device".into(), device).unwrap();
+        Reflect::set(&o, &"device".into(), device)?;
+
+        Ok(Self(o))
+    }
+
+    pub fn with_format(self, format: GPUTextureFormat) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"format".into(),
+            &serde_wasm_bindgen::to_value(&format)?,
+        )?;
+
+        Ok(self)
+    }
+
+    pub fn alpha_mode(self, mode: GPUCanvasAlphaMode) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"alphaMode".into(),
+            &serde_wasm_bindgen::to_value(&mode)?,
+        )?;
+
+        Ok(self)
+    }
+
+    pub fn usage(self, usage: TextureUsages) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"usage".into(), &usage.bits().into())?;
+
+        Ok(self)
+    }
+
+    pub fn view_formats(self, formats: Vec<GPUTextureFormat>) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"viewFormats".into(),
+            &serde_wasm_bindgen::to_value(&formats)?,
+        )?;
+
+        Ok(self)
+    }
+
+    pub fn color_space(self, color_space: PredefinedColorSpace) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"colorSpace".into(),
+            &serde_wasm_bindgen::to_value(&color_space)?,
+        )?;
+
+        Ok(self)
+    }
+
+    pub fn tone_mapping(self, mode: GPUCanvasToneMappingMode) -> Result<Self, GpuError> {
+        let tone_mapping = Object::new();
+        Reflect::set(
+            &tone_mapping,
+            &"mode".into(),
+            &serde_wasm_bindgen::to_value(&mode)?,
+        )?;
+        Reflect::set(&self.0, &"toneMapping".into(), &tone_mapping)?;
+
+        Ok(self)
+    }
+
+    fn get_or_default<T: serde::de::DeserializeOwned + Default>(
+        target: &JsValue,
+        key: &str,
+    ) -> Result<T, GpuError> {
+        let v = Reflect::get(target, &key.into())?;
+        if v.is_undefined() {
+            return Ok(T::default());
+        }
+
+        Ok(serde_wasm_bindgen::from_value(v)?)
+    }
+
+    pub fn get_device(&self) -> Result<GPUDevice, GpuError> {
+        Ok(Reflect::get(&self.0, &"device".into())?.unchecked_into())
+    }
+
+    pub fn get_format(&self) -> Result<GPUTextureFormat, GpuError> {
+        Ok(serde_wasm_bindgen::from_value(Reflect::get(
+            &self.0,
+            &"format".into(),
+        )?)?)
+    }
+
+    pub fn get_alpha_mode(&self) -> Result<GPUCanvasAlphaMode, GpuError> {
+        Self::get_or_default(&self.0, "alphaMode")
+    }
+
+    pub fn get_usage(&self) -> Result<TextureUsages, GpuError> {
+        Ok(Reflect::get(&self.0, &"usage".into())?
+            .as_f64()
+            .map_or(TextureUsages::RENDER_ATTACHMENT, |x| {
+                TextureUsages::from_bits_truncate(x as u32)
+            }))
+    }
+
+    pub fn get_view_formats(&self) -> Result<Vec<GPUTextureFormat>, GpuError> {
+        Self::get_or_default(&self.0, "viewFormats")
+    }
 
-        Self(o)
+    pub fn get_color_space(&self) -> Result<PredefinedColorSpace, GpuError> {
+        Self::get_or_default(&self.0, "colorSpace")
     }
 
-    pub fn with_format(self, format: &str) -> Self {
-        Reflect::set(&self.0, &"format".into(), &format.into()).unwrap();
+    pub fn get_tone_mapping(&self) -> Result<GPUCanvasToneMappingMode, GpuError> {
+        let tone_mapping = Reflect::get(&self.0, &"toneMapping".into())?;
+        if tone_mapping.is_undefined() {
+            return Ok(GPUCanvasToneMappingMode::default());
+        }
 
-        self
+        Self::get_or_default(&tone_mapping, "mode")
     }
 }
 impl From<GPUCanvasConfiguration> for Object {
@@ -149,9 +611,33 @@ impl wasm_bindgen::describe::WasmDescribe for GPUCanvasConfiguration {
     }
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GPUCanvasAlphaMode {
+    #[default]
+    Opaque,
+    Premultiplied,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum PredefinedColorSpace {
+    #[default]
+    Srgb,
+    DisplayP3,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GPUCanvasToneMappingMode {
+    #[default]
+    Standard,
+    Extended,
+}
+
 pub struct GPUShaderModuleDescriptor(Object);
 impl GPUShaderModuleDescriptor {
-    pub fn new(code: &str) -> Result<Self, JsValue> {
+    pub fn new(code: &str) -> Result<Self, GpuError> {
         let o = Object::new();
 
         Reflect::set(&o, &"code".into(), &code.into())?;
@@ -179,7 +665,7 @@ impl wasm_bindgen::describe::WasmDescribe for GPUShaderModuleDescriptor {
 
 pub struct GPUPipelineLayoutDescriptor(Object);
 impl GPUPipelineLayoutDescriptor {
-    pub fn new(bind_group_layouts: Vec<GPUBindGroupLayout>) -> Result<Self, JsValue> {
+    pub fn new(bind_group_layouts: Vec<GPUBindGroupLayout>) -> Result<Self, GpuError> {
         let o = Object::new();
 
         Reflect::set(
@@ -209,10 +695,831 @@ impl wasm_bindgen::describe::WasmDescribe for GPUPipelineLayoutDescriptor {
     }
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GPUTextureFormat {
+    #[serde(rename = "r8unorm")]
+    R8Unorm,
+    #[serde(rename = "r8snorm")]
+    R8Snorm,
+    #[serde(rename = "r8uint")]
+    R8Uint,
+    #[serde(rename = "r8sint")]
+    R8Sint,
+    #[serde(rename = "r16uint")]
+    R16Uint,
+    #[serde(rename = "r16sint")]
+    R16Sint,
+    #[serde(rename = "r16float")]
+    R16Float,
+    #[serde(rename = "rg8unorm")]
+    Rg8Unorm,
+    #[serde(rename = "rg8snorm")]
+    Rg8Snorm,
+    #[serde(rename = "rg8uint")]
+    Rg8Uint,
+    #[serde(rename = "rg8sint")]
+    Rg8Sint,
+    #[serde(rename = "r32uint")]
+    R32Uint,
+    #[serde(rename = "r32sint")]
+    R32Sint,
+    #[serde(rename = "r32float")]
+    R32Float,
+    #[serde(rename = "rg16uint")]
+    Rg16Uint,
+    #[serde(rename = "rg16sint")]
+    Rg16Sint,
+    #[serde(rename = "rg16float")]
+    Rg16Float,
+    #[serde(rename = "rgba8unorm")]
+    Rgba8Unorm,
+    #[serde(rename = "rgba8unorm-srgb")]
+    Rgba8UnormSrgb,
+    #[serde(rename = "rgba8snorm")]
+    Rgba8Snorm,
+    #[serde(rename = "rgba8uint")]
+    Rgba8Uint,
+    #[serde(rename = "rgba8sint")]
+    Rgba8Sint,
+    #[serde(rename = "bgra8unorm")]
+    Bgra8Unorm,
+    #[serde(rename = "bgra8unorm-srgb")]
+    Bgra8UnormSrgb,
+    #[serde(rename = "rgb9e5ufloat")]
+    Rgb9e5Ufloat,
+    #[serde(rename = "rgb10a2uint")]
+    Rgb10a2Uint,
+    #[serde(rename = "rgb10a2unorm")]
+    Rgb10a2Unorm,
+    #[serde(rename = "rg11b10ufloat")]
+    Rg11b10Ufloat,
+    #[serde(rename = "rg32uint")]
+    Rg32Uint,
+    #[serde(rename = "rg32sint")]
+    Rg32Sint,
+    #[serde(rename = "rg32float")]
+    Rg32Float,
+    #[serde(rename = "rgba16uint")]
+    Rgba16Uint,
+    #[serde(rename = "rgba16sint")]
+    Rgba16Sint,
+    #[serde(rename = "rgba16float")]
+    Rgba16Float,
+    #[serde(rename = "rgba32uint")]
+    Rgba32Uint,
+    #[serde(rename = "rgba32sint")]
+    Rgba32Sint,
+    #[serde(rename = "rgba32float")]
+    Rgba32Float,
+    #[serde(rename = "stencil8")]
+    Stencil8,
+    #[serde(rename = "depth16unorm")]
+    Depth16Unorm,
+    #[serde(rename = "depth24plus")]
+    Depth24Plus,
+    #[serde(rename = "depth24plus-stencil8")]
+    Depth24PlusStencil8,
+    #[serde(rename = "depth32float")]
+    Depth32Float,
+    #[serde(rename = "depth32float-stencil8")]
+    Depth32FloatStencil8,
+    #[serde(rename = "bc1-rgba-unorm")]
+    Bc1RgbaUnorm,
+    #[serde(rename = "bc1-rgba-unorm-srgb")]
+    Bc1RgbaUnormSrgb,
+    #[serde(rename = "bc2-rgba-unorm")]
+    Bc2RgbaUnorm,
+    #[serde(rename = "bc2-rgba-unorm-srgb")]
+    Bc2RgbaUnormSrgb,
+    #[serde(rename = "bc3-rgba-unorm")]
+    Bc3RgbaUnorm,
+    #[serde(rename = "bc3-rgba-unorm-srgb")]
+    Bc3RgbaUnormSrgb,
+    #[serde(rename = "bc4-r-unorm")]
+    Bc4RUnorm,
+    #[serde(rename = "bc4-r-snorm")]
+    Bc4RSnorm,
+    #[serde(rename = "bc5-rg-unorm")]
+    Bc5RgUnorm,
+    #[serde(rename = "bc5-rg-snorm")]
+    Bc5RgSnorm,
+    #[serde(rename = "bc6h-rgb-ufloat")]
+    Bc6hRgbUfloat,
+    #[serde(rename = "bc6h-rgb-float")]
+    Bc6hRgbFloat,
+    #[serde(rename = "bc7-rgba-unorm")]
+    Bc7RgbaUnorm,
+    #[serde(rename = "bc7-rgba-unorm-srgb")]
+    Bc7RgbaUnormSrgb,
+    #[serde(rename = "etc2-rgb8unorm")]
+    Etc2Rgb8Unorm,
+    #[serde(rename = "etc2-rgb8unorm-srgb")]
+    Etc2Rgb8UnormSrgb,
+    #[serde(rename = "etc2-rgb8a1unorm")]
+    Etc2Rgb8a1Unorm,
+    #[serde(rename = "etc2-rgb8a1unorm-srgb")]
+    Etc2Rgb8a1UnormSrgb,
+    #[serde(rename = "etc2-rgba8unorm")]
+    Etc2Rgba8Unorm,
+    #[serde(rename = "etc2-rgba8unorm-srgb")]
+    Etc2Rgba8UnormSrgb,
+    #[serde(rename = "eac-r11unorm")]
+    EacR11Unorm,
+    #[serde(rename = "eac-r11snorm")]
+    EacR11Snorm,
+    #[serde(rename = "eac-rg11unorm")]
+    EacRg11Unorm,
+    #[serde(rename = "eac-rg11snorm")]
+    EacRg11Snorm,
+    #[serde(rename = "astc-4x4-unorm")]
+    Astc4x4Unorm,
+    #[serde(rename = "astc-4x4-unorm-srgb")]
+    Astc4x4UnormSrgb,
+    #[serde(rename = "astc-5x4-unorm")]
+    Astc5x4Unorm,
+    #[serde(rename = "astc-5x4-unorm-srgb")]
+    Astc5x4UnormSrgb,
+    #[serde(rename = "astc-5x5-unorm")]
+    Astc5x5Unorm,
+    #[serde(rename = "astc-5x5-unorm-srgb")]
+    Astc5x5UnormSrgb,
+    #[serde(rename = "astc-6x5-unorm")]
+    Astc6x5Unorm,
+    #[serde(rename = "astc-6x5-unorm-srgb")]
+    Astc6x5UnormSrgb,
+    #[serde(rename = "astc-6x6-unorm")]
+    Astc6x6Unorm,
+    #[serde(rename = "astc-6x6-unorm-srgb")]
+    Astc6x6UnormSrgb,
+    #[serde(rename = "astc-8x5-unorm")]
+    Astc8x5Unorm,
+    #[serde(rename = "astc-8x5-unorm-srgb")]
+    Astc8x5UnormSrgb,
+    #[serde(rename = "astc-8x6-unorm")]
+    Astc8x6Unorm,
+    #[serde(rename = "astc-8x6-unorm-srgb")]
+    Astc8x6UnormSrgb,
+    #[serde(rename = "astc-8x8-unorm")]
+    Astc8x8Unorm,
+    #[serde(rename = "astc-8x8-unorm-srgb")]
+    Astc8x8UnormSrgb,
+    #[serde(rename = "astc-10x5-unorm")]
+    Astc10x5Unorm,
+    #[serde(rename = "astc-10x5-unorm-srgb")]
+    Astc10x5UnormSrgb,
+    #[serde(rename = "astc-10x6-unorm")]
+    Astc10x6Unorm,
+    #[serde(rename = "astc-10x6-unorm-srgb")]
+    Astc10x6UnormSrgb,
+    #[serde(rename = "astc-10x8-unorm")]
+    Astc10x8Unorm,
+    #[serde(rename = "astc-10x8-unorm-srgb")]
+    Astc10x8UnormSrgb,
+    #[serde(rename = "astc-10x10-unorm")]
+    Astc10x10Unorm,
+    #[serde(rename = "astc-10x10-unorm-srgb")]
+    Astc10x10UnormSrgb,
+    #[serde(rename = "astc-12x10-unorm")]
+    Astc12x10Unorm,
+    #[serde(rename = "astc-12x10-unorm-srgb")]
+    Astc12x10UnormSrgb,
+    #[serde(rename = "astc-12x12-unorm")]
+    Astc12x12Unorm,
+    #[serde(rename = "astc-12x12-unorm-srgb")]
+    Astc12x12UnormSrgb,
+}
+impl std::str::FromStr for GPUTextureFormat {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use serde::{de::IntoDeserializer, Deserialize};
+
+        Self::deserialize(s.into_deserializer())
+    }
+}
+impl GPUTextureFormat {
+    fn info(self) -> TextureFormatInfo {
+        match self {
+            Self::R8Unorm => TextureFormatInfo::new((1, 1), Some(1), 1, None),
+            Self::R8Snorm => TextureFormatInfo::new((1, 1), Some(1), 1, None),
+            Self::R8Uint => TextureFormatInfo::new((1, 1), Some(1), 1, None),
+            Self::R8Sint => TextureFormatInfo::new((1, 1), Some(1), 1, None),
+            Self::R16Uint => TextureFormatInfo::new((1, 1), Some(2), 1, None),
+            Self::R16Sint => TextureFormatInfo::new((1, 1), Some(2), 1, None),
+            Self::R16Float => TextureFormatInfo::new((1, 1), Some(2), 1, None),
+            Self::Rg8Unorm => TextureFormatInfo::new((1, 1), Some(2), 2, None),
+            Self::Rg8Snorm => TextureFormatInfo::new((1, 1), Some(2), 2, None),
+            Self::Rg8Uint => TextureFormatInfo::new((1, 1), Some(2), 2, None),
+            Self::Rg8Sint => TextureFormatInfo::new((1, 1), Some(2), 2, None),
+            Self::R32Uint => TextureFormatInfo::new((1, 1), Some(4), 1, None),
+            Self::R32Sint => TextureFormatInfo::new((1, 1), Some(4), 1, None),
+            Self::R32Float => TextureFormatInfo::new((1, 1), Some(4), 1, None),
+            Self::Rg16Uint => TextureFormatInfo::new((1, 1), Some(4), 2, None),
+            Self::Rg16Sint => TextureFormatInfo::new((1, 1), Some(4), 2, None),
+            Self::Rg16Float => TextureFormatInfo::new((1, 1), Some(4), 2, None),
+            Self::Rgba8Unorm => TextureFormatInfo::new((1, 1), Some(4), 4, None),
+            Self::Rgba8UnormSrgb => TextureFormatInfo::new((1, 1), Some(4), 4, None),
+            Self::Rgba8Snorm => TextureFormatInfo::new((1, 1), Some(4), 4, None),
+            Self::Rgba8Uint => TextureFormatInfo::new((1, 1), Some(4), 4, None),
+            Self::Rgba8Sint => TextureFormatInfo::new((1, 1), Some(4), 4, None),
+            Self::Bgra8Unorm => TextureFormatInfo::new((1, 1), Some(4), 4, None),
+            Self::Bgra8UnormSrgb => TextureFormatInfo::new((1, 1), Some(4), 4, None),
+            Self::Rgb9e5Ufloat => TextureFormatInfo::new((1, 1), Some(4), 3, None),
+            Self::Rgb10a2Uint => TextureFormatInfo::new((1, 1), Some(4), 4, None),
+            Self::Rgb10a2Unorm => TextureFormatInfo::new((1, 1), Some(4), 4, None),
+            Self::Rg11b10Ufloat => TextureFormatInfo::new((1, 1), Some(4), 3, None),
+            Self::Rg32Uint => TextureFormatInfo::new((1, 1), Some(8), 2, None),
+            Self::Rg32Sint => TextureFormatInfo::new((1, 1), Some(8), 2, None),
+            Self::Rg32Float => TextureFormatInfo::new((1, 1), Some(8), 2, None),
+            Self::Rgba16Uint => TextureFormatInfo::new((1, 1), Some(8), 4, None),
+            Self::Rgba16Sint => TextureFormatInfo::new((1, 1), Some(8), 4, None),
+            Self::Rgba16Float => TextureFormatInfo::new((1, 1), Some(8), 4, None),
+            Self::Rgba32Uint => TextureFormatInfo::new((1, 1), Some(16), 4, None),
+            Self::Rgba32Sint => TextureFormatInfo::new((1, 1), Some(16), 4, None),
+            Self::Rgba32Float => TextureFormatInfo::new((1, 1), Some(16), 4, None),
+            Self::Stencil8 => TextureFormatInfo::new((1, 1), Some(1), 1, None),
+            Self::Depth16Unorm => TextureFormatInfo::new((1, 1), Some(2), 1, None),
+            Self::Depth24Plus => TextureFormatInfo::new((1, 1), None, 1, None),
+            Self::Depth24PlusStencil8 => TextureFormatInfo::new((1, 1), None, 2, None),
+            Self::Depth32Float => TextureFormatInfo::new((1, 1), Some(4), 1, None),
+            Self::Depth32FloatStencil8 => {
+                TextureFormatInfo::new((1, 1), None, 2, Some(GPUFeatureName::Depth32floatStencil8))
+            }
+            Self::Bc1RgbaUnorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(8),
+                4,
+                Some(GPUFeatureName::TextureCompressionBc),
+            ),
+            Self::Bc1RgbaUnormSrgb => TextureFormatInfo::new(
+                (4, 4),
+                Some(8),
+                4,
+                Some(GPUFeatureName::TextureCompressionBc),
+            ),
+            Self::Bc2RgbaUnorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionBc),
+            ),
+            Self::Bc2RgbaUnormSrgb => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionBc),
+            ),
+            Self::Bc3RgbaUnorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionBc),
+            ),
+            Self::Bc3RgbaUnormSrgb => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionBc),
+            ),
+            Self::Bc4RUnorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(8),
+                1,
+                Some(GPUFeatureName::TextureCompressionBc),
+            ),
+            Self::Bc4RSnorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(8),
+                1,
+                Some(GPUFeatureName::TextureCompressionBc),
+            ),
+            Self::Bc5RgUnorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                2,
+                Some(GPUFeatureName::TextureCompressionBc),
+            ),
+            Self::Bc5RgSnorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                2,
+                Some(GPUFeatureName::TextureCompressionBc),
+            ),
+            Self::Bc6hRgbUfloat => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                3,
+                Some(GPUFeatureName::TextureCompressionBc),
+            ),
+            Self::Bc6hRgbFloat => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                3,
+                Some(GPUFeatureName::TextureCompressionBc),
+            ),
+            Self::Bc7RgbaUnorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionBc),
+            ),
+            Self::Bc7RgbaUnormSrgb => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionBc),
+            ),
+            Self::Etc2Rgb8Unorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(8),
+                3,
+                Some(GPUFeatureName::TextureCompressionEtc2),
+            ),
+            Self::Etc2Rgb8UnormSrgb => TextureFormatInfo::new(
+                (4, 4),
+                Some(8),
+                3,
+                Some(GPUFeatureName::TextureCompressionEtc2),
+            ),
+            Self::Etc2Rgb8a1Unorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(8),
+                4,
+                Some(GPUFeatureName::TextureCompressionEtc2),
+            ),
+            Self::Etc2Rgb8a1UnormSrgb => TextureFormatInfo::new(
+                (4, 4),
+                Some(8),
+                4,
+                Some(GPUFeatureName::TextureCompressionEtc2),
+            ),
+            Self::Etc2Rgba8Unorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionEtc2),
+            ),
+            Self::Etc2Rgba8UnormSrgb => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionEtc2),
+            ),
+            Self::EacR11Unorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(8),
+                1,
+                Some(GPUFeatureName::TextureCompressionEtc2),
+            ),
+            Self::EacR11Snorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(8),
+                1,
+                Some(GPUFeatureName::TextureCompressionEtc2),
+            ),
+            Self::EacRg11Unorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                2,
+                Some(GPUFeatureName::TextureCompressionEtc2),
+            ),
+            Self::EacRg11Snorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                2,
+                Some(GPUFeatureName::TextureCompressionEtc2),
+            ),
+            Self::Astc4x4Unorm => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc4x4UnormSrgb => TextureFormatInfo::new(
+                (4, 4),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc5x4Unorm => TextureFormatInfo::new(
+                (5, 4),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc5x4UnormSrgb => TextureFormatInfo::new(
+                (5, 4),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc5x5Unorm => TextureFormatInfo::new(
+                (5, 5),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc5x5UnormSrgb => TextureFormatInfo::new(
+                (5, 5),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc6x5Unorm => TextureFormatInfo::new(
+                (6, 5),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc6x5UnormSrgb => TextureFormatInfo::new(
+                (6, 5),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc6x6Unorm => TextureFormatInfo::new(
+                (6, 6),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc6x6UnormSrgb => TextureFormatInfo::new(
+                (6, 6),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc8x5Unorm => TextureFormatInfo::new(
+                (8, 5),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc8x5UnormSrgb => TextureFormatInfo::new(
+                (8, 5),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc8x6Unorm => TextureFormatInfo::new(
+                (8, 6),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc8x6UnormSrgb => TextureFormatInfo::new(
+                (8, 6),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc8x8Unorm => TextureFormatInfo::new(
+                (8, 8),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc8x8UnormSrgb => TextureFormatInfo::new(
+                (8, 8),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc10x5Unorm => TextureFormatInfo::new(
+                (10, 5),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc10x5UnormSrgb => TextureFormatInfo::new(
+                (10, 5),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc10x6Unorm => TextureFormatInfo::new(
+                (10, 6),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc10x6UnormSrgb => TextureFormatInfo::new(
+                (10, 6),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc10x8Unorm => TextureFormatInfo::new(
+                (10, 8),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc10x8UnormSrgb => TextureFormatInfo::new(
+                (10, 8),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc10x10Unorm => TextureFormatInfo::new(
+                (10, 10),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc10x10UnormSrgb => TextureFormatInfo::new(
+                (10, 10),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc12x10Unorm => TextureFormatInfo::new(
+                (12, 10),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc12x10UnormSrgb => TextureFormatInfo::new(
+                (12, 10),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc12x12Unorm => TextureFormatInfo::new(
+                (12, 12),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+            Self::Astc12x12UnormSrgb => TextureFormatInfo::new(
+                (12, 12),
+                Some(16),
+                4,
+                Some(GPUFeatureName::TextureCompressionAstc),
+            ),
+        }
+    }
+
+    pub fn block_dimensions(self) -> (u32, u32) {
+        self.info().block_dimensions
+    }
+
+    pub fn block_copy_size(self) -> Option<u32> {
+        self.info().block_copy_size
+    }
+
+    pub fn component_count(self) -> u32 {
+        self.info().component_count
+    }
+
+    pub fn required_feature(self) -> Option<GPUFeatureName> {
+        self.info().required_feature
+    }
+
+    pub fn is_compressed(self) -> bool {
+        self.block_dimensions() != (1, 1)
+    }
+
+    pub fn is_depth(self) -> bool {
+        matches!(
+            self,
+            Self::Depth16Unorm
+                | Self::Depth24Plus
+                | Self::Depth24PlusStencil8
+                | Self::Depth32Float
+                | Self::Depth32FloatStencil8
+        )
+    }
+
+    pub fn has_stencil(self) -> bool {
+        matches!(
+            self,
+            Self::Stencil8 | Self::Depth24PlusStencil8 | Self::Depth32FloatStencil8
+        )
+    }
+
+    pub fn is_depth_stencil(self) -> bool {
+        self.is_depth() || self.has_stencil()
+    }
+
+    pub fn is_srgb(self) -> bool {
+        matches!(
+            self,
+            Self::Rgba8UnormSrgb
+                | Self::Bgra8UnormSrgb
+                | Self::Bc1RgbaUnormSrgb
+                | Self::Bc2RgbaUnormSrgb
+                | Self::Bc3RgbaUnormSrgb
+                | Self::Bc7RgbaUnormSrgb
+                | Self::Etc2Rgb8UnormSrgb
+                | Self::Etc2Rgb8a1UnormSrgb
+                | Self::Etc2Rgba8UnormSrgb
+                | Self::Astc4x4UnormSrgb
+                | Self::Astc5x4UnormSrgb
+                | Self::Astc5x5UnormSrgb
+                | Self::Astc6x5UnormSrgb
+                | Self::Astc6x6UnormSrgb
+                | Self::Astc8x5UnormSrgb
+                | Self::Astc8x6UnormSrgb
+                | Self::Astc8x8UnormSrgb
+                | Self::Astc10x5UnormSrgb
+                | Self::Astc10x6UnormSrgb
+                | Self::Astc10x8UnormSrgb
+                | Self::Astc10x10UnormSrgb
+                | Self::Astc12x10UnormSrgb
+                | Self::Astc12x12UnormSrgb
+        )
+    }
+
+    pub fn bytes_per_row(self, width: u32) -> Option<u32> {
+        let (block_width, _) = self.block_dimensions();
+
+        self.block_copy_size()
+            .map(|size| width.div_ceil(block_width) * size)
+    }
+
+    pub fn aligned_bytes_per_row(self, width: u32) -> Option<u32> {
+        self.bytes_per_row(width)
+            .map(|x| x.next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT))
+    }
+}
+
+struct TextureFormatInfo {
+    block_dimensions: (u32, u32),
+    block_copy_size: Option<u32>,
+    component_count: u32,
+    required_feature: Option<GPUFeatureName>,
+}
+impl TextureFormatInfo {
+    const fn new(
+        block_dimensions: (u32, u32),
+        block_copy_size: Option<u32>,
+        component_count: u32,
+        required_feature: Option<GPUFeatureName>,
+    ) -> Self {
+        Self {
+            block_dimensions,
+            block_copy_size,
+            component_count,
+            required_feature,
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GPUVertexFormat {
+    #[serde(rename = "uint8")]
+    Uint8,
+    #[serde(rename = "uint8x2")]
+    Uint8x2,
+    #[serde(rename = "uint8x4")]
+    Uint8x4,
+    #[serde(rename = "sint8")]
+    Sint8,
+    #[serde(rename = "sint8x2")]
+    Sint8x2,
+    #[serde(rename = "sint8x4")]
+    Sint8x4,
+    #[serde(rename = "unorm8")]
+    Unorm8,
+    #[serde(rename = "unorm8x2")]
+    Unorm8x2,
+    #[serde(rename = "unorm8x4")]
+    Unorm8x4,
+    #[serde(rename = "snorm8")]
+    Snorm8,
+    #[serde(rename = "snorm8x2")]
+    Snorm8x2,
+    #[serde(rename = "snorm8x4")]
+    Snorm8x4,
+    #[serde(rename = "uint16")]
+    Uint16,
+    #[serde(rename = "uint16x2")]
+    Uint16x2,
+    #[serde(rename = "uint16x4")]
+    Uint16x4,
+    #[serde(rename = "sint16")]
+    Sint16,
+    #[serde(rename = "sint16x2")]
+    Sint16x2,
+    #[serde(rename = "sint16x4")]
+    Sint16x4,
+    #[serde(rename = "unorm16")]
+    Unorm16,
+    #[serde(rename = "unorm16x2")]
+    Unorm16x2,
+    #[serde(rename = "unorm16x4")]
+    Unorm16x4,
+    #[serde(rename = "snorm16")]
+    Snorm16,
+    #[serde(rename = "snorm16x2")]
+    Snorm16x2,
+    #[serde(rename = "snorm16x4")]
+    Snorm16x4,
+    #[serde(rename = "float16")]
+    Float16,
+    #[serde(rename = "float16x2")]
+    Float16x2,
+    #[serde(rename = "float16x4")]
+    Float16x4,
+    #[serde(rename = "float32")]
+    Float32,
+    #[serde(rename = "float32x2")]
+    Float32x2,
+    #[serde(rename = "float32x3")]
+    Float32x3,
+    #[serde(rename = "float32x4")]
+    Float32x4,
+    #[serde(rename = "uint32")]
+    Uint32,
+    #[serde(rename = "uint32x2")]
+    Uint32x2,
+    #[serde(rename = "uint32x3")]
+    Uint32x3,
+    #[serde(rename = "uint32x4")]
+    Uint32x4,
+    #[serde(rename = "sint32")]
+    Sint32,
+    #[serde(rename = "sint32x2")]
+    Sint32x2,
+    #[serde(rename = "sint32x3")]
+    Sint32x3,
+    #[serde(rename = "sint32x4")]
+    Sint32x4,
+    #[serde(rename = "unorm10-10-10-2")]
+    Unorm1010102,
+    #[serde(rename = "unorm8x4-bgra")]
+    Unorm8x4Bgra,
+}
+impl std::str::FromStr for GPUVertexFormat {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use serde::{de::IntoDeserializer, Deserialize};
+
+        Self::deserialize(s.into_deserializer())
+    }
+}
+impl GPUVertexFormat {
+    fn layout(self) -> (usize, u32) {
+        match self {
+            Self::Uint8 => (1, 1),
+            Self::Uint8x2 => (2, 2),
+            Self::Uint8x4 => (4, 4),
+            Self::Sint8 => (1, 1),
+            Self::Sint8x2 => (2, 2),
+            Self::Sint8x4 => (4, 4),
+            Self::Unorm8 => (1, 1),
+            Self::Unorm8x2 => (2, 2),
+            Self::Unorm8x4 => (4, 4),
+            Self::Snorm8 => (1, 1),
+            Self::Snorm8x2 => (2, 2),
+            Self::Snorm8x4 => (4, 4),
+            Self::Uint16 => (2, 1),
+            Self::Uint16x2 => (4, 2),
+            Self::Uint16x4 => (8, 4),
+            Self::Sint16 => (2, 1),
+            Self::Sint16x2 => (4, 2),
+            Self::Sint16x4 => (8, 4),
+            Self::Unorm16 => (2, 1),
+            Self::Unorm16x2 => (4, 2),
+            Self::Unorm16x4 => (8, 4),
+            Self::Snorm16 => (2, 1),
+            Self::Snorm16x2 => (4, 2),
+            Self::Snorm16x4 => (8, 4),
+            Self::Float16 => (2, 1),
+            Self::Float16x2 => (4, 2),
+            Self::Float16x4 => (8, 4),
+            Self::Float32 => (4, 1),
+            Self::Float32x2 => (8, 2),
+            Self::Float32x3 => (12, 3),
+            Self::Float32x4 => (16, 4),
+            Self::Uint32 => (4, 1),
+            Self::Uint32x2 => (8, 2),
+            Self::Uint32x3 => (12, 3),
+            Self::Uint32x4 => (16, 4),
+            Self::Sint32 => (4, 1),
+            Self::Sint32x2 => (8, 2),
+            Self::Sint32x3 => (12, 3),
+            Self::Sint32x4 => (16, 4),
+            Self::Unorm1010102 => (4, 4),
+            Self::Unorm8x4Bgra => (4, 4),
+        }
+    }
+
+    pub fn byte_size(self) -> usize {
+        self.layout().0
+    }
+
+    pub fn component_count(self) -> u32 {
+        self.layout().1
+    }
+}
+
 #[derive(serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GPUVertexAttribute {
-    pub format: String,
+    pub format: GPUVertexFormat,
     pub offset: usize,
     pub shader_location: u32,
 }
@@ -230,28 +1537,116 @@ pub struct GPURenderPipelineVertexProperties<'s> {
     pub buffers: Option<Vec<GPUVertexBufferLayout>>,
 }
 impl GPURenderPipelineVertexProperties<'_> {
-    pub fn into_object(self) -> Object {
+    pub fn into_object(self) -> Result<Object, GpuError> {
         let o = Object::new();
 
-        Reflect::set(&o, &"entryPoint".into(), &self.entry_point.into()).unwrap();
-        Reflect::set(&o, &"module".into(), self.module).unwrap();
+        Reflect::set(&o, &"entryPoint".into(), &self.entry_point.into())?;
+        Reflect::set(&o, &"module".into(), self.module)?;
         if let Some(bs) = self.buffers {
-            Reflect::set(
-                &o,
-                &"buffers".into(),
-                &serde_wasm_bindgen::to_value(&bs).unwrap(),
-            )
-            .unwrap();
+            Reflect::set(&o, &"buffers".into(), &serde_wasm_bindgen::to_value(&bs)?)?;
         }
 
-        o
+        Ok(o)
+    }
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum GPUBlendOperation {
+    Add,
+    Subtract,
+    ReverseSubtract,
+    Min,
+    Max,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum GPUBlendFactor {
+    Zero,
+    One,
+    Src,
+    OneMinusSrc,
+    SrcAlpha,
+    OneMinusSrcAlpha,
+    Dst,
+    OneMinusDst,
+    DstAlpha,
+    OneMinusDstAlpha,
+    SrcAlphaSaturated,
+    Constant,
+    OneMinusConstant,
+    Src1,
+    OneMinusSrc1,
+    Src1Alpha,
+    OneMinusSrc1Alpha,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GPUBlendComponent {
+    pub operation: GPUBlendOperation,
+    pub src_factor: GPUBlendFactor,
+    pub dst_factor: GPUBlendFactor,
+}
+impl GPUBlendComponent {
+    pub const REPLACE: Self = Self {
+        operation: GPUBlendOperation::Add,
+        src_factor: GPUBlendFactor::One,
+        dst_factor: GPUBlendFactor::Zero,
+    };
+    pub const OVER: Self = Self {
+        operation: GPUBlendOperation::Add,
+        src_factor: GPUBlendFactor::One,
+        dst_factor: GPUBlendFactor::OneMinusSrcAlpha,
+    };
+}
+impl Default for GPUBlendComponent {
+    fn default() -> Self {
+        Self::REPLACE
     }
 }
 
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct GPUBlendState {
+    pub color: GPUBlendComponent,
+    pub alpha: GPUBlendComponent,
+}
+impl GPUBlendState {
+    pub const REPLACE: Self = Self {
+        color: GPUBlendComponent::REPLACE,
+        alpha: GPUBlendComponent::REPLACE,
+    };
+    pub const ALPHA_BLENDING: Self = Self {
+        color: GPUBlendComponent {
+            operation: GPUBlendOperation::Add,
+            src_factor: GPUBlendFactor::SrcAlpha,
+            dst_factor: GPUBlendFactor::OneMinusSrcAlpha,
+        },
+        alpha: GPUBlendComponent::OVER,
+    };
+    pub const PREMULTIPLIED_ALPHA_BLENDING: Self = Self {
+        color: GPUBlendComponent::OVER,
+        alpha: GPUBlendComponent::OVER,
+    };
+}
+
 #[derive(serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GPURenderPipelineFragmentTarget {
-    pub format: String,
+    pub format: GPUTextureFormat,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blend: Option<GPUBlendState>,
+    pub write_mask: ColorWrites,
+}
+impl GPURenderPipelineFragmentTarget {
+    pub fn new(format: GPUTextureFormat) -> Self {
+        Self {
+            format,
+            blend: None,
+            write_mask: ColorWrites::ALL,
+        }
+    }
 }
 
 pub struct GPURenderPipelineFragmentProperties<'s> {
@@ -260,40 +1655,204 @@ pub struct GPURenderPipelineFragmentProperties<'s> {
     pub targets: Vec<GPURenderPipelineFragmentTarget>,
 }
 impl GPURenderPipelineFragmentProperties<'_> {
-    pub fn into_object(self) -> Object {
+    pub fn into_object(self) -> Result<Object, GpuError> {
         let o = Object::new();
 
-        Reflect::set(&o, &"entryPoint".into(), &self.entry_point.into()).unwrap();
-        Reflect::set(&o, &"module".into(), self.module).unwrap();
+        Reflect::set(&o, &"entryPoint".into(), &self.entry_point.into())?;
+        Reflect::set(&o, &"module".into(), self.module)?;
         Reflect::set(
             &o,
             &"targets".into(),
-            &serde_wasm_bindgen::to_value(&self.targets).unwrap(),
-        )
-        .unwrap();
+            &serde_wasm_bindgen::to_value(&self.targets)?,
+        )?;
 
-        o
+        Ok(o)
     }
 }
 
-pub struct GPURenderPipelineDescriptor(Object);
-impl GPURenderPipelineDescriptor {
-    pub fn new(layout: &GPUPipelineLayout, vertex: GPURenderPipelineVertexProperties) -> Self {
-        let o = Object::new();
+#[wasm_bindgen]
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GPUIndexFormat {
+    Uint16 = "uint16",
+    Uint32 = "uint32",
+}
 
-        Reflect::set(&o, &"layout".into(), layout).unwrap();
-        Reflect::set(&o, &"vertex".into(), &vertex.into_object()).unwrap();
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum GPUPrimitiveTopology {
+    PointList,
+    LineList,
+    LineStrip,
+    #[default]
+    TriangleList,
+    TriangleStrip,
+}
 
-        Self(o)
-    }
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GPUFrontFace {
+    #[default]
+    Ccw,
+    Cw,
+}
 
-    pub fn fragment(self, fragment: GPURenderPipelineFragmentProperties) -> Self {
-        Reflect::set(&self.0, &"fragment".into(), &fragment.into_object()).unwrap();
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GPUCullMode {
+    #[default]
+    None,
+    Front,
+    Back,
+}
 
-        self
-    }
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GPUPrimitiveState {
+    pub topology: GPUPrimitiveTopology,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strip_index_format: Option<GPUIndexFormat>,
+    pub front_face: GPUFrontFace,
+    pub cull_mode: GPUCullMode,
+    pub unclipped_depth: bool,
 }
-impl From<GPURenderPipelineDescriptor> for Object {
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GPUMultisampleState {
+    pub count: u32,
+    pub mask: u32,
+    pub alpha_to_coverage_enabled: bool,
+}
+impl Default for GPUMultisampleState {
+    fn default() -> Self {
+        Self {
+            count: 1,
+            mask: 0xffff_ffff,
+            alpha_to_coverage_enabled: false,
+        }
+    }
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum GPUStencilOperation {
+    #[default]
+    Keep,
+    Zero,
+    Replace,
+    Invert,
+    IncrementClamp,
+    DecrementClamp,
+    IncrementWrap,
+    DecrementWrap,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GPUStencilFaceState {
+    pub compare: GPUCompareFunction,
+    pub fail_op: GPUStencilOperation,
+    pub depth_fail_op: GPUStencilOperation,
+    pub pass_op: GPUStencilOperation,
+}
+impl Default for GPUStencilFaceState {
+    fn default() -> Self {
+        Self {
+            compare: GPUCompareFunction::Always,
+            fail_op: GPUStencilOperation::default(),
+            depth_fail_op: GPUStencilOperation::default(),
+            pass_op: GPUStencilOperation::default(),
+        }
+    }
+}
+
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GPUDepthStencilState {
+    pub format: GPUTextureFormat,
+    pub depth_write_enabled: bool,
+    pub depth_compare: GPUCompareFunction,
+    pub stencil_front: GPUStencilFaceState,
+    pub stencil_back: GPUStencilFaceState,
+    pub stencil_read_mask: u32,
+    pub stencil_write_mask: u32,
+    pub depth_bias: i32,
+    pub depth_bias_slope_scale: f32,
+    pub depth_bias_clamp: f32,
+}
+impl GPUDepthStencilState {
+    pub fn new(
+        format: GPUTextureFormat,
+        depth_write_enabled: bool,
+        depth_compare: GPUCompareFunction,
+    ) -> Self {
+        Self {
+            format,
+            depth_write_enabled,
+            depth_compare,
+            stencil_front: GPUStencilFaceState::default(),
+            stencil_back: GPUStencilFaceState::default(),
+            stencil_read_mask: 0xffff_ffff,
+            stencil_write_mask: 0xffff_ffff,
+            depth_bias: 0,
+            depth_bias_slope_scale: 0.0,
+            depth_bias_clamp: 0.0,
+        }
+    }
+}
+
+pub struct GPURenderPipelineDescriptor(Object);
+impl GPURenderPipelineDescriptor {
+    pub fn new(
+        layout: &GPUPipelineLayout,
+        vertex: GPURenderPipelineVertexProperties,
+    ) -> Result<Self, GpuError> {
+        let o = Object::new();
+
+        Reflect::set(&o, &"layout".into(), layout)?;
+        Reflect::set(&o, &"vertex".into(), &vertex.into_object()?.into())?;
+
+        Ok(Self(o))
+    }
+
+    pub fn fragment(self, fragment: GPURenderPipelineFragmentProperties) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"fragment".into(), &fragment.into_object()?.into())?;
+
+        Ok(self)
+    }
+
+    pub fn multisample(self, multisample: GPUMultisampleState) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"multisample".into(),
+            &serde_wasm_bindgen::to_value(&multisample)?,
+        )?;
+
+        Ok(self)
+    }
+
+    pub fn depth_stencil(self, depth_stencil: GPUDepthStencilState) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"depthStencil".into(),
+            &serde_wasm_bindgen::to_value(&depth_stencil)?,
+        )?;
+
+        Ok(self)
+    }
+
+    pub fn primitive(self, primitive: GPUPrimitiveState) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"primitive".into(),
+            &serde_wasm_bindgen::to_value(&primitive)?,
+        )?;
+
+        Ok(self)
+    }
+}
+impl From<GPURenderPipelineDescriptor> for Object {
     fn from(value: GPURenderPipelineDescriptor) -> Self {
         value.0
     }
@@ -311,22 +1870,120 @@ impl wasm_bindgen::describe::WasmDescribe for GPURenderPipelineDescriptor {
     }
 }
 
+pub struct GPUComputePipelineComputeProperties<'s> {
+    pub entry_point: String,
+    pub module: &'s GPUShaderModule,
+}
+impl GPUComputePipelineComputeProperties<'_> {
+    pub fn into_object(self) -> Result<Object, GpuError> {
+        let o = Object::new();
+
+        Reflect::set(&o, &"entryPoint".into(), &self.entry_point.into())?;
+        Reflect::set(&o, &"module".into(), self.module)?;
+
+        Ok(o)
+    }
+}
+
+pub struct GPUComputePipelineDescriptor(Object);
+impl GPUComputePipelineDescriptor {
+    pub fn new(
+        layout: &GPUPipelineLayout,
+        compute: GPUComputePipelineComputeProperties,
+    ) -> Result<Self, GpuError> {
+        let o = Object::new();
+
+        Reflect::set(&o, &"layout".into(), layout)?;
+        Reflect::set(&o, &"compute".into(), &compute.into_object()?.into())?;
+
+        Ok(Self(o))
+    }
+
+    pub fn label(self, label: &str) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"label".into(), &label.into())?;
+
+        Ok(self)
+    }
+}
+impl From<GPUComputePipelineDescriptor> for Object {
+    fn from(value: GPUComputePipelineDescriptor) -> Self {
+        value.0
+    }
+}
+impl IntoWasmAbi for GPUComputePipelineDescriptor {
+    type Abi = <Object as IntoWasmAbi>::Abi;
+
+    fn into_abi(self) -> Self::Abi {
+        Object::into_abi(self.0)
+    }
+}
+impl wasm_bindgen::describe::WasmDescribe for GPUComputePipelineDescriptor {
+    fn describe() {
+        Object::describe()
+    }
+}
+
+pub struct GPUComputePassDescriptor {
+    pub label: Option<String>,
+}
+impl GPUComputePassDescriptor {
+    pub fn into_object(self) -> Result<Object, GpuError> {
+        let obj = Object::new();
+
+        if let Some(label) = self.label {
+            Reflect::set(&obj, &"label".into(), &label.into())?;
+        }
+
+        Ok(obj)
+    }
+}
+
 pub struct GPURenderBundleEncoderDescriptor(Object);
 impl GPURenderBundleEncoderDescriptor {
-    pub fn new(color_formats: Vec<String>) -> Self {
+    pub fn new(color_formats: Vec<GPUTextureFormat>) -> Result<Self, GpuError> {
         let o = Object::new();
 
         Reflect::set(
             &o,
             &"colorFormats".into(),
-            &color_formats
-                .into_iter()
-                .map(Into::<JsValue>::into)
-                .collect::<Array>(),
-        )
-        .unwrap();
+            &serde_wasm_bindgen::to_value(&color_formats)?,
+        )?;
+
+        Ok(Self(o))
+    }
 
-        Self(o)
+    pub fn label(self, label: &str) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"label".into(), &label.into())?;
+
+        Ok(self)
+    }
+
+    pub fn depth_stencil_format(self, format: GPUTextureFormat) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"depthStencilFormat".into(),
+            &serde_wasm_bindgen::to_value(&format)?,
+        )?;
+
+        Ok(self)
+    }
+
+    pub fn sample_count(self, count: u32) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"sampleCount".into(), &count.into())?;
+
+        Ok(self)
+    }
+
+    pub fn depth_read_only(self) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"depthReadOnly".into(), &true.into())?;
+
+        Ok(self)
+    }
+
+    pub fn stencil_read_only(self) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"stencilReadOnly".into(), &true.into())?;
+
+        Ok(self)
     }
 }
 impl From<GPURenderBundleEncoderDescriptor> for Object {
@@ -347,273 +2004,2541 @@ impl wasm_bindgen::describe::WasmDescribe for GPURenderBundleEncoderDescriptor {
     }
 }
 
-#[wasm_bindgen]
-extern "C" {
-    pub type HTMLCanvasElement;
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GPUExtent3D {
+    pub width: u32,
+    pub height: u32,
+    pub depth_or_array_layers: u32,
+}
+impl GPUExtent3D {
+    pub fn into_object(self) -> Result<Object, GpuError> {
+        Ok(serde_wasm_bindgen::to_value(&self)?.unchecked_into())
+    }
 
-    #[wasm_bindgen(method, js_name = getContext)]
-    pub fn get_context(element: &HTMLCanvasElement, context_type: &str) -> JsValue;
+    pub const fn new_2d(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        }
+    }
 }
 
-#[wasm_bindgen]
-extern "C" {
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GPUTextureDimension {
+    #[serde(rename = "1d")]
+    D1,
+    #[serde(rename = "2d")]
+    D2,
+    #[serde(rename = "3d")]
+    D3,
+}
 
-    pub type GPUCanvasContext;
+pub struct GPUTextureDescriptor(Object);
+impl GPUTextureDescriptor {
+    pub fn new(
+        size: GPUExtent3D,
+        format: GPUTextureFormat,
+        usage: TextureUsages,
+    ) -> Result<Self, GpuError> {
+        let o = Object::new();
 
-    #[wasm_bindgen(method)]
-    pub fn configure(ctx: &GPUCanvasContext, configuration: GPUCanvasConfiguration);
-    #[wasm_bindgen(method, catch, js_name = getCurrentTexture)]
-    pub fn get_current_texture(ctx: &GPUCanvasContext) -> Result<GPUTexture, JsValue>;
+        Reflect::set(&o, &"size".into(), &serde_wasm_bindgen::to_value(&size)?)?;
+        Reflect::set(
+            &o,
+            &"format".into(),
+            &serde_wasm_bindgen::to_value(&format)?,
+        )?;
+        Reflect::set(&o, &"usage".into(), &usage.bits().into())?;
 
-    pub type GPU;
-    pub type GPUAdapter;
-    pub type GPUSupportedFeatures;
-    pub type GPUDevice;
-    pub type GPUShaderModule;
-    pub type GPUBindGroupLayout;
-    pub type GPUPipelineLayout;
-    pub type GPURenderPipeline;
+        Ok(Self(o))
+    }
+
+    pub fn label(self, label: &str) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"label".into(), &label.into())?;
+
+        Ok(self)
+    }
+
+    pub fn mip_level_count(self, count: u32) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"mipLevelCount".into(), &count.into())?;
+
+        Ok(self)
+    }
+
+    pub fn sample_count(self, count: u32) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"sampleCount".into(), &count.into())?;
+
+        Ok(self)
+    }
+
+    pub fn dimension(self, dimension: GPUTextureDimension) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"dimension".into(),
+            &serde_wasm_bindgen::to_value(&dimension)?,
+        )?;
+
+        Ok(self)
+    }
+
+    pub fn view_formats(self, formats: Vec<GPUTextureFormat>) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"viewFormats".into(),
+            &serde_wasm_bindgen::to_value(&formats)?,
+        )?;
+
+        Ok(self)
+    }
+}
+impl From<GPUTextureDescriptor> for Object {
+    fn from(value: GPUTextureDescriptor) -> Self {
+        value.0
+    }
+}
+impl IntoWasmAbi for GPUTextureDescriptor {
+    type Abi = <Object as IntoWasmAbi>::Abi;
+
+    fn into_abi(self) -> Self::Abi {
+        Object::into_abi(self.0)
+    }
+}
+impl wasm_bindgen::describe::WasmDescribe for GPUTextureDescriptor {
+    fn describe() {
+        Object::describe()
+    }
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GPUTextureViewDimension {
+    #[serde(rename = "1d")]
+    D1,
+    #[serde(rename = "2d")]
+    D2,
+    #[serde(rename = "2d-array")]
+    D2Array,
+    #[serde(rename = "cube")]
+    Cube,
+    #[serde(rename = "cube-array")]
+    CubeArray,
+    #[serde(rename = "3d")]
+    D3,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum GPUTextureAspect {
+    All,
+    StencilOnly,
+    DepthOnly,
+}
+
+pub struct GPUTextureViewDescriptor(Object);
+impl GPUTextureViewDescriptor {
+    pub fn new() -> Self {
+        Self(Object::new())
+    }
+
+    pub fn label(self, label: &str) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"label".into(), &label.into())?;
+
+        Ok(self)
+    }
+
+    pub fn format(self, format: GPUTextureFormat) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"format".into(),
+            &serde_wasm_bindgen::to_value(&format)?,
+        )?;
+
+        Ok(self)
+    }
+
+    pub fn dimension(self, dimension: GPUTextureViewDimension) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"dimension".into(),
+            &serde_wasm_bindgen::to_value(&dimension)?,
+        )?;
+
+        Ok(self)
+    }
+
+    pub fn aspect(self, aspect: GPUTextureAspect) -> Result<Self, GpuError> {
+        Reflect::set(
+            &self.0,
+            &"aspect".into(),
+            &serde_wasm_bindgen::to_value(&aspect)?,
+        )?;
+
+        Ok(self)
+    }
+
+    pub fn mip_levels(self, base: u32, count: Option<u32>) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"baseMipLevel".into(), &base.into())?;
+        if let Some(count) = count {
+            Reflect::set(&self.0, &"mipLevelCount".into(), &count.into())?;
+        }
+
+        Ok(self)
+    }
+
+    pub fn array_layers(self, base: u32, count: Option<u32>) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"baseArrayLayer".into(), &base.into())?;
+        if let Some(count) = count {
+            Reflect::set(&self.0, &"arrayLayerCount".into(), &count.into())?;
+        }
+
+        Ok(self)
+    }
+}
+impl Default for GPUTextureViewDescriptor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl From<GPUTextureViewDescriptor> for Object {
+    fn from(value: GPUTextureViewDescriptor) -> Self {
+        value.0
+    }
+}
+impl IntoWasmAbi for GPUTextureViewDescriptor {
+    type Abi = <Object as IntoWasmAbi>::Abi;
+
+    fn into_abi(self) -> Self::Abi {
+        Object::into_abi(self.0)
+    }
+}
+impl wasm_bindgen::describe::WasmDescribe for GPUTextureViewDescriptor {
+    fn describe() {
+        Object::describe()
+    }
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum GPUAddressMode {
+    #[default]
+    ClampToEdge,
+    Repeat,
+    MirrorRepeat,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GPUFilterMode {
+    #[default]
+    Nearest,
+    Linear,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GPUMipmapFilterMode {
+    #[default]
+    Nearest,
+    Linear,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum GPUCompareFunction {
+    Never,
+    Less,
+    Equal,
+    LessEqual,
+    Greater,
+    NotEqual,
+    GreaterEqual,
+    Always,
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GPUSamplerDescriptor {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub address_mode_u: GPUAddressMode,
+    pub address_mode_v: GPUAddressMode,
+    pub address_mode_w: GPUAddressMode,
+    pub mag_filter: GPUFilterMode,
+    pub min_filter: GPUFilterMode,
+    pub mipmap_filter: GPUMipmapFilterMode,
+    pub lod_min_clamp: f32,
+    pub lod_max_clamp: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compare: Option<GPUCompareFunction>,
+    pub max_anisotropy: u16,
+}
+impl Default for GPUSamplerDescriptor {
+    fn default() -> Self {
+        Self {
+            label: None,
+            address_mode_u: GPUAddressMode::default(),
+            address_mode_v: GPUAddressMode::default(),
+            address_mode_w: GPUAddressMode::default(),
+            mag_filter: GPUFilterMode::default(),
+            min_filter: GPUFilterMode::default(),
+            mipmap_filter: GPUMipmapFilterMode::default(),
+            lod_min_clamp: 0.0,
+            lod_max_clamp: 32.0,
+            compare: None,
+            max_anisotropy: 1,
+        }
+    }
+}
+impl GPUSamplerDescriptor {
+    pub fn into_object(self) -> Result<Object, GpuError> {
+        Ok(serde_wasm_bindgen::to_value(&self)?.unchecked_into())
+    }
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum GPUBufferBindingType {
+    #[default]
+    Uniform,
+    Storage,
+    ReadOnlyStorage,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GPUBufferBindingLayout {
+    #[serde(rename = "type")]
+    pub ty: GPUBufferBindingType,
+    pub has_dynamic_offset: bool,
+    pub min_binding_size: usize,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum GPUSamplerBindingType {
+    #[default]
+    Filtering,
+    NonFiltering,
+    Comparison,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GPUSamplerBindingLayout {
+    #[serde(rename = "type")]
+    pub ty: GPUSamplerBindingType,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum GPUTextureSampleType {
+    #[default]
+    Float,
+    UnfilterableFloat,
+    Depth,
+    Sint,
+    Uint,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GPUTextureBindingLayout {
+    pub sample_type: GPUTextureSampleType,
+    pub view_dimension: GPUTextureViewDimension,
+    pub multisampled: bool,
+}
+impl Default for GPUTextureBindingLayout {
+    fn default() -> Self {
+        Self {
+            sample_type: GPUTextureSampleType::default(),
+            view_dimension: GPUTextureViewDimension::D2,
+            multisampled: false,
+        }
+    }
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum GPUStorageTextureAccess {
+    #[default]
+    WriteOnly,
+    ReadOnly,
+    ReadWrite,
+}
+
+#[derive(serde::Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GPUStorageTextureBindingLayout {
+    pub access: GPUStorageTextureAccess,
+    pub format: GPUTextureFormat,
+    pub view_dimension: GPUTextureViewDimension,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct GPUExternalTextureBindingLayout {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GPUBindingLayoutType {
+    Buffer(GPUBufferBindingLayout),
+    Sampler(GPUSamplerBindingLayout),
+    Texture(GPUTextureBindingLayout),
+    StorageTexture(GPUStorageTextureBindingLayout),
+    ExternalTexture(GPUExternalTextureBindingLayout),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GPUBindGroupLayoutEntry {
+    pub binding: u32,
+    pub visibility: ShaderStages,
+    pub ty: GPUBindingLayoutType,
+}
+impl serde::Serialize for GPUBindGroupLayoutEntry {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("GPUBindGroupLayoutEntry", 3)?;
+        s.serialize_field("binding", &self.binding)?;
+        s.serialize_field("visibility", &self.visibility)?;
+        match &self.ty {
+            GPUBindingLayoutType::Buffer(x) => s.serialize_field("buffer", x)?,
+            GPUBindingLayoutType::Sampler(x) => s.serialize_field("sampler", x)?,
+            GPUBindingLayoutType::Texture(x) => s.serialize_field("texture", x)?,
+            GPUBindingLayoutType::StorageTexture(x) => s.serialize_field("storageTexture", x)?,
+            GPUBindingLayoutType::ExternalTexture(x) => s.serialize_field("externalTexture", x)?,
+        }
+        s.end()
+    }
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GPUBindGroupLayoutDescriptor {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub entries: Vec<GPUBindGroupLayoutEntry>,
+}
+impl GPUBindGroupLayoutDescriptor {
+    pub fn into_object(self) -> Result<Object, GpuError> {
+        Ok(serde_wasm_bindgen::to_value(&self)?.unchecked_into())
+    }
+}
+
+pub enum GPUBindingResource<'r> {
+    Buffer {
+        buffer: &'r GPUBuffer,
+        offset: usize,
+        size: Option<usize>,
+    },
+    Sampler(&'r GPUSampler),
+    TextureView(&'r GPUTextureView),
+    ExternalTexture(&'r GPUExternalTexture),
+}
+impl GPUBindingResource<'_> {
+    pub fn into_js_value(self) -> Result<JsValue, GpuError> {
+        match self {
+            Self::Buffer {
+                buffer,
+                offset,
+                size,
+            } => {
+                let o = Object::new();
+
+                Reflect::set(&o, &"buffer".into(), buffer)?;
+                Reflect::set(&o, &"offset".into(), &offset.into())?;
+                if let Some(size) = size {
+                    Reflect::set(&o, &"size".into(), &size.into())?;
+                }
+
+                Ok(o.into())
+            }
+            Self::Sampler(x) => Ok(x.into()),
+            Self::TextureView(x) => Ok(x.into()),
+            Self::ExternalTexture(x) => Ok(x.into()),
+        }
+    }
+}
+
+pub struct GPUBindGroupEntry<'r> {
+    pub binding: u32,
+    pub resource: GPUBindingResource<'r>,
+}
+impl GPUBindGroupEntry<'_> {
+    pub fn into_object(self) -> Result<Object, GpuError> {
+        let o = Object::new();
+
+        Reflect::set(&o, &"binding".into(), &self.binding.into())?;
+        Reflect::set(&o, &"resource".into(), &self.resource.into_js_value()?)?;
+
+        Ok(o)
+    }
+}
+
+pub struct GPUBindGroupDescriptor(Object);
+impl GPUBindGroupDescriptor {
+    pub fn new(
+        layout: &GPUBindGroupLayout,
+        entries: Vec<GPUBindGroupEntry>,
+    ) -> Result<Self, GpuError> {
+        let o = Object::new();
+
+        Reflect::set(&o, &"layout".into(), layout)?;
+        Reflect::set(
+            &o,
+            &"entries".into(),
+            &entries
+                .into_iter()
+                .map(GPUBindGroupEntry::into_object)
+                .collect::<Result<Array, _>>()?
+                .into(),
+        )?;
+
+        Ok(Self(o))
+    }
+
+    pub fn label(self, label: &str) -> Result<Self, GpuError> {
+        Reflect::set(&self.0, &"label".into(), &label.into())?;
+
+        Ok(self)
+    }
+}
+impl From<GPUBindGroupDescriptor> for Object {
+    fn from(value: GPUBindGroupDescriptor) -> Self {
+        value.0
+    }
+}
+impl IntoWasmAbi for GPUBindGroupDescriptor {
+    type Abi = <Object as IntoWasmAbi>::Abi;
+
+    fn into_abi(self) -> Self::Abi {
+        Object::into_abi(self.0)
+    }
+}
+impl wasm_bindgen::describe::WasmDescribe for GPUBindGroupDescriptor {
+    fn describe() {
+        Object::describe()
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GPUDrawIndirectArgs {
+    pub vertex_count: u32,
+    pub instance_count: u32,
+    pub first_vertex: u32,
+    pub first_instance: u32,
+}
+impl GPUDrawIndirectArgs {
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GPUDrawIndexedIndirectArgs {
+    pub index_count: u32,
+    pub instance_count: u32,
+    pub first_index: u32,
+    pub base_vertex: i32,
+    pub first_instance: u32,
+}
+impl GPUDrawIndexedIndirectArgs {
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GPUDispatchIndirectArgs {
+    pub workgroup_count_x: u32,
+    pub workgroup_count_y: u32,
+    pub workgroup_count_z: u32,
+}
+impl GPUDispatchIndirectArgs {
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct GPUOrigin3D {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GPUImageDataLayout {
+    pub offset: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytes_per_row: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rows_per_image: Option<u32>,
+}
+impl GPUImageDataLayout {
+    pub fn into_object(self) -> Result<Object, GpuError> {
+        Ok(serde_wasm_bindgen::to_value(&self)?.unchecked_into())
+    }
+}
+
+pub const COPY_BYTES_PER_ROW_ALIGNMENT: u32 = 256;
+
+pub struct GPUImageCopyBuffer<'b> {
+    pub buffer: &'b GPUBuffer,
+    pub layout: GPUImageDataLayout,
+}
+impl GPUImageCopyBuffer<'_> {
+    pub fn into_object(self) -> Result<Object, GpuError> {
+        let obj = self.layout.into_object()?;
+
+        Reflect::set(&obj, &"buffer".into(), self.buffer)?;
+
+        Ok(obj)
+    }
+}
+
+pub struct GPUImageCopyTexture<'t> {
+    pub texture: &'t GPUTexture,
+    pub mip_level: u32,
+    pub origin: GPUOrigin3D,
+    pub aspect: GPUTextureAspect,
+}
+impl<'t> GPUImageCopyTexture<'t> {
+    pub fn new(texture: &'t GPUTexture) -> Self {
+        Self {
+            texture,
+            mip_level: 0,
+            origin: GPUOrigin3D::default(),
+            aspect: GPUTextureAspect::All,
+        }
+    }
+
+    pub fn into_object(self) -> Result<Object, GpuError> {
+        let obj = Object::new();
+
+        Reflect::set(&obj, &"texture".into(), self.texture)?;
+        Reflect::set(&obj, &"mipLevel".into(), &self.mip_level.into())?;
+        Reflect::set(
+            &obj,
+            &"origin".into(),
+            &serde_wasm_bindgen::to_value(&self.origin)?,
+        )?;
+        Reflect::set(
+            &obj,
+            &"aspect".into(),
+            &serde_wasm_bindgen::to_value(&self.aspect)?,
+        )?;
+
+        Ok(obj)
+    }
+}
+
+pub struct MultisampleColorTarget {
+    pub texture: GPUTexture,
+    pub view: GPUTextureView,
+    pub sample_count: u32,
+}
+impl MultisampleColorTarget {
+    pub fn new(
+        device: &GPUDevice,
+        size: GPUExtent3D,
+        format: GPUTextureFormat,
+        sample_count: u32,
+    ) -> Result<Self, GpuError> {
+        let texture = device.create_texture(
+            GPUTextureDescriptor::new(size, format, TextureUsages::RENDER_ATTACHMENT)?
+                .label("Multisampled Color Target")?
+                .sample_count(sample_count)?,
+        )?;
+        let view = texture.create_view()?;
+
+        Ok(Self {
+            texture,
+            view,
+            sample_count,
+        })
+    }
+
+    pub fn for_canvas(
+        device: &GPUDevice,
+        canvas: &HTMLCanvasElement,
+        format: GPUTextureFormat,
+        sample_count: u32,
+    ) -> Result<Self, GpuError> {
+        Self::new(
+            device,
+            GPUExtent3D::new_2d(canvas.width(), canvas.height()),
+            format,
+            sample_count,
+        )
+    }
+
+    pub fn matches_size(&self, width: u32, height: u32) -> bool {
+        self.texture.width() == width && self.texture.height() == height
+    }
+
+    pub fn multisample_state(&self) -> GPUMultisampleState {
+        GPUMultisampleState {
+            count: self.sample_count,
+            ..Default::default()
+        }
+    }
+
+    pub fn color_attachment(
+        &self,
+        resolve_target: &GPUTextureView,
+    ) -> Result<GPURenderPassColorAttachment, GpuError> {
+        GPURenderPassColorAttachment::new(&self.view)?
+            .resolve_to(resolve_target)?
+            .store_op(GPUStoreOp::Discard)
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    pub type HTMLCanvasElement;
+
+    #[wasm_bindgen(method, js_name = getContext)]
+    pub fn get_context(element: &HTMLCanvasElement, context_type: &str) -> JsValue;
+    #[wasm_bindgen(method, getter)]
+    pub fn width(element: &HTMLCanvasElement) -> u32;
+    #[wasm_bindgen(method, getter)]
+    pub fn height(element: &HTMLCanvasElement) -> u32;
+    #[wasm_bindgen(method, setter)]
+    pub fn set_width(element: &HTMLCanvasElement, width: u32);
+    #[wasm_bindgen(method, setter)]
+    pub fn set_height(element: &HTMLCanvasElement, height: u32);
+}
+
+#[wasm_bindgen]
+extern "C" {
+    pub type OffscreenCanvas;
+
+    #[wasm_bindgen(method, js_name = getContext)]
+    pub fn get_context(canvas: &OffscreenCanvas, context_type: &str) -> JsValue;
+    #[wasm_bindgen(method, getter)]
+    pub fn width(canvas: &OffscreenCanvas) -> u32;
+    #[wasm_bindgen(method, getter)]
+    pub fn height(canvas: &OffscreenCanvas) -> u32;
+    #[wasm_bindgen(method, setter)]
+    pub fn set_width(canvas: &OffscreenCanvas, width: u32);
+    #[wasm_bindgen(method, setter)]
+    pub fn set_height(canvas: &OffscreenCanvas, height: u32);
+}
+
+#[wasm_bindgen]
+extern "C" {
+
+    pub type GPUCanvasContext;
+
+    #[wasm_bindgen(method, catch)]
+    pub fn configure(
+        ctx: &GPUCanvasContext,
+        configuration: GPUCanvasConfiguration,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = getCurrentTexture)]
+    pub fn get_current_texture(ctx: &GPUCanvasContext) -> Result<GPUTexture, GpuError>;
+    #[wasm_bindgen(method)]
+    pub fn unconfigure(ctx: &GPUCanvasContext);
+    #[wasm_bindgen(method, js_name = getConfiguration)]
+    fn get_configuration_raw(ctx: &GPUCanvasContext) -> JsValue;
+
+    pub type GPU;
+    pub type GPUAdapter;
+    pub type GPUSupportedFeatures;
+    pub type GPUSupportedLimits;
+    pub type GPUDevice;
+    pub type GPUShaderModule;
+    pub type GPUSampler;
+    pub type GPUBindGroupLayout;
+    pub type GPUBindGroup;
+    pub type GPUExternalTexture;
+    pub type GPUPipelineLayout;
+    pub type GPURenderPipeline;
+    pub type GPUComputePipeline;
+
+    #[wasm_bindgen(method, js_name = requestAdapter)]
+    async fn request_adapter(this: &GPU) -> JsValue;
+    #[wasm_bindgen(method, js_name = requestAdapter, catch)]
+    async fn request_adapter_with_options_raw(
+        this: &GPU,
+        options: &Object,
+    ) -> Result<JsValue, GpuError>;
+    #[wasm_bindgen(method, js_name = getPreferredCanvasFormat)]
+    fn get_preferred_canvas_format_string(gpu: &GPU) -> String;
+
+    #[wasm_bindgen(method, getter = features)]
+    fn features_raw(this: &GPUAdapter) -> GPUSupportedFeatures;
+    #[wasm_bindgen(method, getter = limits)]
+    fn limits_raw(this: &GPUAdapter) -> GPUSupportedLimits;
+    #[wasm_bindgen(method, getter, js_name = isFallbackAdapter)]
+    fn is_fallback_adapter_raw(this: &GPUAdapter) -> Option<bool>;
+    #[wasm_bindgen(method, getter = info)]
+    fn info_raw(this: &GPUAdapter) -> JsValue;
+    #[wasm_bindgen(method, js_name = requestAdapterInfo, catch)]
+    async fn request_adapter_info_raw(this: &GPUAdapter) -> Result<JsValue, GpuError>;
+    #[wasm_bindgen(method, js_name = requestDevice, catch)]
+    async fn request_device(this: &GPUAdapter) -> Result<JsValue, GpuError>;
+    #[wasm_bindgen(method, js_name = requestDevice, catch)]
+    async fn request_device_with_descriptor_raw(
+        this: &GPUAdapter,
+        descriptor: &Object,
+    ) -> Result<JsValue, GpuError>;
+    #[wasm_bindgen(method, getter)]
+    pub fn queue(this: &GPUDevice) -> GPUQueue;
+    #[wasm_bindgen(method, getter = features)]
+    fn features_raw(this: &GPUDevice) -> GPUSupportedFeatures;
+    #[wasm_bindgen(method, getter = limits)]
+    fn limits_raw(this: &GPUDevice) -> GPUSupportedLimits;
+
+    #[wasm_bindgen(method, js_name = createBuffer, catch)]
+    fn create_buffer(
+        this: &GPUDevice,
+        descriptor: GPUBufferCreateDescription,
+    ) -> Result<GPUBuffer, GpuError>;
+    #[wasm_bindgen(method, js_name = createTexture, catch)]
+    pub fn create_texture(
+        device: &GPUDevice,
+        descriptor: GPUTextureDescriptor,
+    ) -> Result<GPUTexture, GpuError>;
+    #[wasm_bindgen(method, js_name = createSampler, catch)]
+    fn create_sampler_raw(device: &GPUDevice, descriptor: &Object) -> Result<GPUSampler, GpuError>;
+    #[wasm_bindgen(method, js_name = createBindGroupLayout, catch)]
+    fn create_bind_group_layout_raw(
+        device: &GPUDevice,
+        descriptor: &Object,
+    ) -> Result<GPUBindGroupLayout, GpuError>;
+    #[wasm_bindgen(method, js_name = createBindGroup, catch)]
+    pub fn create_bind_group(
+        device: &GPUDevice,
+        descriptor: GPUBindGroupDescriptor,
+    ) -> Result<GPUBindGroup, GpuError>;
+    #[wasm_bindgen(method, js_name = createCommandEncoder, catch)]
+    pub fn create_command_encoder(this: &GPUDevice) -> Result<GPUCommandEncoder, GpuError>;
+    #[wasm_bindgen(method, js_name = createCommandEncoder, catch)]
+    pub fn create_command_encoder_with_description(
+        this: &GPUDevice,
+        descriptor: GPUCommandEncoderCreateDescription,
+    ) -> Result<GPUCommandEncoder, GpuError>;
+    #[wasm_bindgen(method, js_name = createShaderModule, catch)]
+    pub fn create_shader_module(
+        device: &GPUDevice,
+        descriptor: GPUShaderModuleDescriptor,
+    ) -> Result<GPUShaderModule, GpuError>;
+    #[wasm_bindgen(method, js_name = createPipelineLayout, catch)]
+    pub fn create_pipeline_layout(
+        device: &GPUDevice,
+        descriptor: GPUPipelineLayoutDescriptor,
+    ) -> Result<GPUPipelineLayout, GpuError>;
+    #[wasm_bindgen(method, js_name = createRenderPipeline, catch)]
+    pub fn create_render_pipeline(
+        device: &GPUDevice,
+        descriptor: GPURenderPipelineDescriptor,
+    ) -> Result<GPURenderPipeline, GpuError>;
+    #[wasm_bindgen(method, js_name = createComputePipeline, catch)]
+    pub fn create_compute_pipeline(
+        device: &GPUDevice,
+        descriptor: GPUComputePipelineDescriptor,
+    ) -> Result<GPUComputePipeline, GpuError>;
+    #[wasm_bindgen(method, js_name = createRenderBundleEncoder, catch)]
+    pub fn create_render_bundle_encoder(
+        device: &GPUDevice,
+        descriptor: GPURenderBundleEncoderDescriptor,
+    ) -> Result<GPURenderBundleEncoder, GpuError>;
+
+    #[wasm_bindgen(method, js_name = pushErrorScope)]
+    pub fn push_error_scope(device: &GPUDevice, filter: GPUErrorFilter);
+    #[wasm_bindgen(method, js_name = popErrorScope, catch)]
+    async fn pop_error_scope_raw(device: &GPUDevice) -> Result<JsValue, GpuError>;
+    #[wasm_bindgen(method, js_name = addEventListener)]
+    fn add_event_listener(device: &GPUDevice, ty: &str, listener: &js_sys::Function);
+    #[wasm_bindgen(method, js_name = removeEventListener)]
+    fn remove_event_listener(device: &GPUDevice, ty: &str, listener: &js_sys::Function);
+    #[wasm_bindgen(method, getter = lost)]
+    fn lost_promise(device: &GPUDevice) -> js_sys::Promise;
+    #[wasm_bindgen(method)]
+    pub fn destroy(device: &GPUDevice);
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GPUDeviceLostReason {
+    Unknown,
+    Destroyed,
+}
+impl std::str::FromStr for GPUDeviceLostReason {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use serde::{de::IntoDeserializer, Deserialize};
+
+        Self::deserialize(s.into_deserializer())
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    pub type GPUDeviceLostInfo;
+
+    #[wasm_bindgen(method, getter = reason)]
+    fn reason_raw(this: &GPUDeviceLostInfo) -> Option<String>;
+    #[wasm_bindgen(method, getter)]
+    pub fn message(this: &GPUDeviceLostInfo) -> String;
+}
+impl GPUDeviceLostInfo {
+    /// reasons added to the spec after this binding are reported as `Unknown`
+    pub fn reason(&self) -> GPUDeviceLostReason {
+        self.reason_raw()
+            .and_then(|r| r.parse().ok())
+            .unwrap_or(GPUDeviceLostReason::Unknown)
+    }
+}
+impl From<GPUDeviceLostInfo> for GpuError {
+    fn from(value: GPUDeviceLostInfo) -> Self {
+        Self::DeviceLost {
+            reason: value.reason_raw().unwrap_or_else(|| "unknown".into()),
+            message: value.message(),
+        }
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    pub type GPUBuffer;
+
+    #[wasm_bindgen(method)]
+    pub fn destroy(this: &GPUBuffer);
+    #[wasm_bindgen(method, js_name = mapAsync, catch)]
+    pub async fn map_async(this: &GPUBuffer, mode: MapMode) -> Result<JsValue, GpuError>;
+    #[wasm_bindgen(method, js_name = mapAsync, catch)]
+    pub async fn map_range_async(
+        this: &GPUBuffer,
+        mode: MapMode,
+        offset: usize,
+        size: usize,
+    ) -> Result<JsValue, GpuError>;
+    #[wasm_bindgen(method)]
+    pub fn unmap(this: &GPUBuffer);
+    #[wasm_bindgen(method, js_name = getMappedRange, catch)]
+    pub fn get_mapped_range(
+        this: &GPUBuffer,
+        offset: usize,
+        size: usize,
+    ) -> Result<ArrayBuffer, GpuError>;
+    #[wasm_bindgen(method, js_name = getMappedRange, catch)]
+    pub fn get_mapped_range_from(this: &GPUBuffer, offset: usize) -> Result<ArrayBuffer, GpuError>;
+    #[wasm_bindgen(method, js_name = getMappedRange, catch)]
+    pub fn get_mapped_range_full(this: &GPUBuffer) -> Result<ArrayBuffer, GpuError>;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn usage(this: &GPUBuffer) -> BufferUsages;
+    #[wasm_bindgen(method, getter)]
+    pub fn size(this: &GPUBuffer) -> usize;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    pub type GPUTexture;
+    pub type GPUTextureView;
+
+    #[wasm_bindgen(method)]
+    pub fn destroy(texture: &GPUTexture);
+    #[wasm_bindgen(method, js_name = createView, catch)]
+    pub fn create_view(texture: &GPUTexture) -> Result<GPUTextureView, GpuError>;
+    #[wasm_bindgen(method, js_name = createView, catch)]
+    pub fn create_view_with_descriptor(
+        texture: &GPUTexture,
+        descriptor: GPUTextureViewDescriptor,
+    ) -> Result<GPUTextureView, GpuError>;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn width(texture: &GPUTexture) -> u32;
+    #[wasm_bindgen(method, getter)]
+    pub fn height(texture: &GPUTexture) -> u32;
+    #[wasm_bindgen(method, getter, js_name = depthOrArrayLayers)]
+    pub fn depth_or_array_layers(texture: &GPUTexture) -> u32;
+    #[wasm_bindgen(method, getter, js_name = mipLevelCount)]
+    pub fn mip_level_count(texture: &GPUTexture) -> u32;
+    #[wasm_bindgen(method, getter, js_name = sampleCount)]
+    pub fn sample_count(texture: &GPUTexture) -> u32;
+    #[wasm_bindgen(method, getter)]
+    pub fn dimension(texture: &GPUTexture) -> String;
+    #[wasm_bindgen(method, getter, js_name = format)]
+    fn format_string(texture: &GPUTexture) -> String;
+    #[wasm_bindgen(method, getter)]
+    pub fn usage(texture: &GPUTexture) -> TextureUsages;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    pub type GPUCommandEncoder;
+    pub type GPUCommandBuffer;
+
+    #[wasm_bindgen(method, catch)]
+    pub fn finish(this: &GPUCommandEncoder) -> Result<GPUCommandBuffer, GpuError>;
+    #[wasm_bindgen(method, catch)]
+    pub fn finish_with_descriptor(
+        this: &GPUCommandEncoder,
+        descriptor: GPUCommandBufferDescriptor,
+    ) -> Result<GPUCommandBuffer, GpuError>;
+
+    #[wasm_bindgen(method, js_name = copyBufferToBuffer, catch)]
+    pub fn copy_buffer_to_buffer(
+        this: &GPUCommandEncoder,
+        source: &GPUBuffer,
+        source_offset: usize,
+        destination: &GPUBuffer,
+        destination_offset: usize,
+        size: usize,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, js_name = copyBufferToTexture, catch)]
+    fn copy_buffer_to_texture_raw(
+        this: &GPUCommandEncoder,
+        source: &Object,
+        destination: &Object,
+        copy_size: &Object,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, js_name = copyTextureToBuffer, catch)]
+    fn copy_texture_to_buffer_raw(
+        this: &GPUCommandEncoder,
+        source: &Object,
+        destination: &Object,
+        copy_size: &Object,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, js_name = copyTextureToTexture, catch)]
+    fn copy_texture_to_texture_raw(
+        this: &GPUCommandEncoder,
+        source: &Object,
+        destination: &Object,
+        copy_size: &Object,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, js_name = clearBuffer, catch)]
+    pub fn clear_buffer(this: &GPUCommandEncoder, buffer: &GPUBuffer) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, js_name = clearBuffer, catch)]
+    pub fn clear_buffer_range(
+        this: &GPUCommandEncoder,
+        buffer: &GPUBuffer,
+        offset: usize,
+        size: usize,
+    ) -> Result<(), GpuError>;
+
+    #[wasm_bindgen(method, js_name = beginRenderPass, catch)]
+    fn begin_render_pass_raw(
+        this: &GPUCommandEncoder,
+        descriptor: &Object,
+    ) -> Result<GPURenderPassEncoder, GpuError>;
+    #[wasm_bindgen(method, js_name = beginComputePass, catch)]
+    pub fn begin_compute_pass(this: &GPUCommandEncoder) -> Result<GPUComputePassEncoder, GpuError>;
+    #[wasm_bindgen(method, js_name = beginComputePass, catch)]
+    fn begin_compute_pass_with_descriptor_raw(
+        this: &GPUCommandEncoder,
+        descriptor: &Object,
+    ) -> Result<GPUComputePassEncoder, GpuError>;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    pub type GPUQueue;
+
+    #[wasm_bindgen(method, catch)]
+    pub fn submit(queue: &GPUQueue, command_buffers: Vec<GPUCommandBuffer>)
+        -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = writeBuffer)]
+    pub fn write_buffer(
+        queue: &GPUQueue,
+        buffer: &GPUBuffer,
+        buffer_offset: usize,
+        data: &[u8],
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = writeTexture)]
+    fn write_texture_raw(
+        queue: &GPUQueue,
+        destination: &Object,
+        data: &[u8],
+        data_layout: &Object,
+        size: &Object,
+    ) -> Result<(), GpuError>;
+}
+
+impl GPUQueue {
+    pub fn write_texture(
+        &self,
+        destination: GPUImageCopyTexture,
+        data: &[u8],
+        data_layout: GPUImageDataLayout,
+        size: GPUExtent3D,
+    ) -> Result<(), GpuError> {
+        self.write_texture_raw(
+            &destination.into_object()?,
+            data,
+            &data_layout.into_object()?,
+            &size.into_object()?,
+        )
+    }
+
+    pub fn write_buffer_data<T: bytemuck::Pod>(
+        &self,
+        buffer: &GPUBuffer,
+        buffer_offset: usize,
+        data: &[T],
+    ) -> Result<(), GpuError> {
+        self.write_buffer(buffer, buffer_offset, bytemuck::cast_slice(data))
+    }
+
+    pub fn write_texture_data<T: bytemuck::Pod>(
+        &self,
+        destination: GPUImageCopyTexture,
+        data: &[T],
+        data_layout: GPUImageDataLayout,
+        size: GPUExtent3D,
+    ) -> Result<(), GpuError> {
+        self.write_texture(destination, bytemuck::cast_slice(data), data_layout, size)
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    pub type GPURenderPassEncoder;
+
+    #[wasm_bindgen(method, catch)]
+    pub fn end(encoder: &GPURenderPassEncoder) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setPipeline)]
+    pub fn set_pipeline(
+        encoder: &GPURenderPassEncoder,
+        pipeline: &GPURenderPipeline,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setScissorRect)]
+    pub fn set_scissor_rect(
+        encoder: &GPURenderPassEncoder,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setViewport)]
+    pub fn set_viewport(
+        encoder: &GPURenderPassEncoder,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        min_depth: f32,
+        max_depth: f32,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setBindGroup)]
+    pub fn set_bind_group(
+        encoder: &GPURenderPassEncoder,
+        index: u32,
+        bind_group: &GPUBindGroup,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setBindGroup)]
+    pub fn set_bind_group_with_dynamic_offsets_data(
+        encoder: &GPURenderPassEncoder,
+        index: u32,
+        bind_group: &GPUBindGroup,
+        dynamic_offsets_data: &[u32],
+        dynamic_offsets_data_start: u32,
+        dynamic_offsets_data_length: u32,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setBlendConstant)]
+    pub fn set_blend_constant(
+        encoder: &GPURenderPassEncoder,
+        color: &[f64],
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, js_name = setStencilReference)]
+    pub fn set_stencil_reference(encoder: &GPURenderPassEncoder, reference: u32);
+    #[wasm_bindgen(method, catch, js_name = setVertexBuffer)]
+    pub fn set_vertex_buffer(
+        encoder: &GPURenderPassEncoder,
+        slot: u32,
+        buffer: &GPUBuffer,
+        offset: usize,
+        size: usize,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setIndexBuffer)]
+    pub fn set_index_buffer(
+        encoder: &GPURenderPassEncoder,
+        buffer: &GPUBuffer,
+        index_format: GPUIndexFormat,
+        offset: usize,
+        size: usize,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method)]
+    pub fn draw(
+        encoder: &GPURenderPassEncoder,
+        vertex_count: u32,
+        instance_count: u32,
+        first_vertex: u32,
+        first_instance: u32,
+    );
+    #[wasm_bindgen(method, js_name = drawIndexed)]
+    pub fn draw_indexed(
+        encoder: &GPURenderPassEncoder,
+        index_count: u32,
+        instance_count: u32,
+        first_index: u32,
+        base_vertex: i32,
+        first_instance: u32,
+    );
+    #[wasm_bindgen(method, js_name = drawIndirect)]
+    pub fn draw_indirect(
+        encoder: &GPURenderPassEncoder,
+        indirect_buffer: &GPUBuffer,
+        indirect_offset: usize,
+    );
+    #[wasm_bindgen(method, js_name = drawIndexedIndirect)]
+    pub fn draw_indexed_indirect(
+        encoder: &GPURenderPassEncoder,
+        indirect_buffer: &GPUBuffer,
+        indirect_offset: usize,
+    );
+    #[wasm_bindgen(method, js_name = executeBundles, catch)]
+    pub fn execute_bundles(
+        encoder: &GPURenderPassEncoder,
+        bundles: Vec<GPURenderBundle>,
+    ) -> Result<(), GpuError>;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    pub type GPURenderBundleEncoder;
+    pub type GPURenderBundle;
+
+    #[wasm_bindgen(method, catch)]
+    pub fn finish(encoder: &GPURenderBundleEncoder) -> Result<GPURenderBundle, GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setPipeline)]
+    pub fn set_pipeline(
+        encoder: &GPURenderBundleEncoder,
+        pipeline: &GPURenderPipeline,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setBindGroup)]
+    pub fn set_bind_group(
+        encoder: &GPURenderBundleEncoder,
+        index: u32,
+        bind_group: &GPUBindGroup,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setBindGroup)]
+    pub fn set_bind_group_with_dynamic_offsets_data(
+        encoder: &GPURenderBundleEncoder,
+        index: u32,
+        bind_group: &GPUBindGroup,
+        dynamic_offsets_data: &[u32],
+        dynamic_offsets_data_start: u32,
+        dynamic_offsets_data_length: u32,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setVertexBuffer)]
+    pub fn set_vertex_buffer(
+        encoder: &GPURenderBundleEncoder,
+        slot: u32,
+        buffer: &GPUBuffer,
+        offset: usize,
+        size: usize,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setIndexBuffer)]
+    pub fn set_index_buffer(
+        encoder: &GPURenderBundleEncoder,
+        buffer: &GPUBuffer,
+        index_format: GPUIndexFormat,
+        offset: usize,
+        size: usize,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method)]
+    pub fn draw(
+        encoder: &GPURenderBundleEncoder,
+        vertex_count: u32,
+        instance_count: u32,
+        first_vertex: u32,
+        first_instance: u32,
+    );
+    #[wasm_bindgen(method, js_name = drawIndexed)]
+    pub fn draw_indexed(
+        encoder: &GPURenderBundleEncoder,
+        index_count: u32,
+        instance_count: u32,
+        first_index: u32,
+        base_vertex: i32,
+        first_instance: u32,
+    );
+    #[wasm_bindgen(method, js_name = drawIndirect)]
+    pub fn draw_indirect(
+        encoder: &GPURenderBundleEncoder,
+        indirect_buffer: &GPUBuffer,
+        indirect_offset: usize,
+    );
+    #[wasm_bindgen(method, js_name = drawIndexedIndirect)]
+    pub fn draw_indexed_indirect(
+        encoder: &GPURenderBundleEncoder,
+        indirect_buffer: &GPUBuffer,
+        indirect_offset: usize,
+    );
+}
+
+#[wasm_bindgen]
+extern "C" {
+    pub type GPUComputePassEncoder;
+
+    #[wasm_bindgen(method, catch)]
+    pub fn end(encoder: &GPUComputePassEncoder) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setPipeline)]
+    pub fn set_pipeline(
+        encoder: &GPUComputePassEncoder,
+        pipeline: &GPUComputePipeline,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setBindGroup)]
+    pub fn set_bind_group(
+        encoder: &GPUComputePassEncoder,
+        index: u32,
+        bind_group: &GPUBindGroup,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, catch, js_name = setBindGroup)]
+    pub fn set_bind_group_with_dynamic_offsets_data(
+        encoder: &GPUComputePassEncoder,
+        index: u32,
+        bind_group: &GPUBindGroup,
+        dynamic_offsets_data: &[u32],
+        dynamic_offsets_data_start: u32,
+        dynamic_offsets_data_length: u32,
+    ) -> Result<(), GpuError>;
+    #[wasm_bindgen(method, js_name = dispatchWorkgroups)]
+    pub fn dispatch_workgroups(
+        encoder: &GPUComputePassEncoder,
+        workgroup_count_x: u32,
+        workgroup_count_y: u32,
+        workgroup_count_z: u32,
+    );
+    #[wasm_bindgen(method, js_name = dispatchWorkgroupsIndirect)]
+    pub fn dispatch_workgroups_indirect(
+        encoder: &GPUComputePassEncoder,
+        indirect_buffer: &GPUBuffer,
+        indirect_offset: usize,
+    );
+}
+
+impl GPUDevice {
+    pub fn create_sampler(&self, descriptor: GPUSamplerDescriptor) -> Result<GPUSampler, GpuError> {
+        self.create_sampler_raw(&descriptor.into_object()?)
+    }
+
+    pub fn create_bind_group_layout(
+        &self,
+        descriptor: GPUBindGroupLayoutDescriptor,
+    ) -> Result<GPUBindGroupLayout, GpuError> {
+        self.create_bind_group_layout_raw(&descriptor.into_object()?)
+    }
+
+    pub async fn pop_error_scope(&self) -> Result<Option<GpuError>, GpuError> {
+        let e = self.pop_error_scope_raw().await?;
+
+        Ok((!e.is_null() && !e.is_undefined()).then(|| GpuError::from(e)))
+    }
+
+    pub fn on_uncaptured_error(
+        &self,
+        mut handler: impl FnMut(GpuError) + 'static,
+    ) -> UncapturedErrorSubscription {
+        let listener = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+            let error = Reflect::get(&event, &"error".into()).unwrap_or(event);
+            handler(GpuError::from(error));
+        });
+        self.add_event_listener("uncapturederror", listener.as_ref().unchecked_ref());
+
+        UncapturedErrorSubscription {
+            device: JsValue::clone(self).unchecked_into(),
+            listener: Some(listener),
+        }
+    }
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum GPUPowerPreference {
+    LowPower,
+    HighPerformance,
+}
+
+#[derive(serde::Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GPURequestAdapterOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power_preference: Option<GPUPowerPreference>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_fallback_adapter: Option<bool>,
+}
+impl GPURequestAdapterOptions {
+    pub fn into_object(self) -> Result<Object, GpuError> {
+        Ok(serde_wasm_bindgen::to_value(&self)?.unchecked_into())
+    }
+}
+
+#[derive(
+    serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum GPUFeatureName {
+    DepthClipControl,
+    Depth32floatStencil8,
+    TextureCompressionBc,
+    #[serde(rename = "texture-compression-bc-sliced-3d")]
+    TextureCompressionBcSliced3d,
+    TextureCompressionEtc2,
+    TextureCompressionAstc,
+    #[serde(rename = "texture-compression-astc-sliced-3d")]
+    TextureCompressionAstcSliced3d,
+    TimestampQuery,
+    IndirectFirstInstance,
+    ShaderF16,
+    Rg11b10ufloatRenderable,
+    Bgra8unormStorage,
+    Float32Filterable,
+    Float32Blendable,
+    ClipDistances,
+    DualSourceBlending,
+    Subgroups,
+}
+impl std::str::FromStr for GPUFeatureName {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use serde::{de::IntoDeserializer, Deserialize};
+
+        Self::deserialize(s.into_deserializer())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Features(BTreeSet<GPUFeatureName>);
+impl Features {
+    pub fn from_supported(features: &GPUSupportedFeatures) -> Result<Self, GpuError> {
+        let Some(iter) = js_sys::try_iter(features)? else {
+            return Ok(Self::default());
+        };
+
+        let mut set = BTreeSet::new();
+        for x in iter {
+            // names unknown to this crate (vendor extensions etc.) are skipped
+            if let Some(f) = x?.as_string().and_then(|x| x.parse().ok()) {
+                set.insert(f);
+            }
+        }
+
+        Ok(Self(set))
+    }
+
+    pub fn contains(&self, feature: GPUFeatureName) -> bool {
+        self.0.contains(&feature)
+    }
+
+    pub fn insert(&mut self, feature: GPUFeatureName) -> bool {
+        self.0.insert(feature)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = GPUFeatureName> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self(self.0.intersection(&other.0).copied().collect())
+    }
+
+    pub fn meets_requirements(&self, required: &Self) -> bool {
+        self.0.is_superset(&required.0)
+    }
+
+    pub fn check_requirements(&self, required: &Self) -> Result<(), GpuError> {
+        match required.iter().find(|f| !self.contains(*f)) {
+            Some(f) => Err(GpuError::UnsupportedFeature(f)),
+            None => Ok(()),
+        }
+    }
+}
+impl FromIterator<GPUFeatureName> for Features {
+    fn from_iter<T: IntoIterator<Item = GPUFeatureName>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+impl IntoIterator for Features {
+    type Item = GPUFeatureName;
+    type IntoIter = std::collections::btree_set::IntoIter<GPUFeatureName>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+// alignment limits ("min*") are satisfied by larger values, the rest by smaller ones
+fn limit_satisfied(name: &str, requested: u64, supported: u64) -> bool {
+    if name.starts_with("min") {
+        requested >= supported
+    } else {
+        requested <= supported
+    }
+}
+
+macro_rules! define_limits {
+    ($($field: ident: $t: ty = $default: expr => $name: literal),* $(,)?) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct Limits {
+            $(pub $field: $t),*
+        }
+        impl Default for Limits {
+            fn default() -> Self {
+                Self {
+                    $($field: $default),*
+                }
+            }
+        }
+        impl Limits {
+            pub const NAMES: &'static [&'static str] = &[$($name),*];
+
+            pub fn from_supported(limits: &GPUSupportedLimits) -> Self {
+                let mut l = Self::default();
+                $(
+                    if let Some(v) = Reflect::get(limits, &$name.into()).ok().and_then(|v| v.as_f64()) {
+                        l.$field = v as $t;
+                    }
+                )*
+
+                l
+            }
+
+            pub fn entries(&self) -> Vec<(&'static str, u64)> {
+                vec![$(($name, self.$field as u64)),*]
+            }
+
+            pub fn get(&self, name: &str) -> Option<u64> {
+                match name {
+                    $($name => Some(self.$field as u64),)*
+                    _ => None,
+                }
+            }
+
+            pub fn intersection(&self, other: &Self) -> Self {
+                Self {
+                    $(
+                        $field: if $name.starts_with("min") {
+                            self.$field.max(other.$field)
+                        } else {
+                            self.$field.min(other.$field)
+                        }
+                    ),*
+                }
+            }
+
+            pub fn check_requirements(&self, required: &Self) -> Result<(), GpuError> {
+                $(
+                    if !limit_satisfied($name, required.$field as u64, self.$field as u64) {
+                        return Err(GpuError::UnsupportedLimit {
+                            name: $name.into(),
+                            requested: required.$field as u64,
+                            supported: self.$field as u64,
+                        });
+                    }
+                )*
+
+                Ok(())
+            }
+        }
+    };
+}
+define_limits! {
+    max_texture_dimension_1d: u32 = 8192 => "maxTextureDimension1D",
+    max_texture_dimension_2d: u32 = 8192 => "maxTextureDimension2D",
+    max_texture_dimension_3d: u32 = 2048 => "maxTextureDimension3D",
+    max_texture_array_layers: u32 = 256 => "maxTextureArrayLayers",
+    max_bind_groups: u32 = 4 => "maxBindGroups",
+    max_bind_groups_plus_vertex_buffers: u32 = 24 => "maxBindGroupsPlusVertexBuffers",
+    max_bindings_per_bind_group: u32 = 1000 => "maxBindingsPerBindGroup",
+    max_dynamic_uniform_buffers_per_pipeline_layout: u32 = 8 => "maxDynamicUniformBuffersPerPipelineLayout",
+    max_dynamic_storage_buffers_per_pipeline_layout: u32 = 4 => "maxDynamicStorageBuffersPerPipelineLayout",
+    max_sampled_textures_per_shader_stage: u32 = 16 => "maxSampledTexturesPerShaderStage",
+    max_samplers_per_shader_stage: u32 = 16 => "maxSamplersPerShaderStage",
+    max_storage_buffers_per_shader_stage: u32 = 8 => "maxStorageBuffersPerShaderStage",
+    max_storage_textures_per_shader_stage: u32 = 4 => "maxStorageTexturesPerShaderStage",
+    max_uniform_buffers_per_shader_stage: u32 = 12 => "maxUniformBuffersPerShaderStage",
+    max_uniform_buffer_binding_size: u64 = 65536 => "maxUniformBufferBindingSize",
+    max_storage_buffer_binding_size: u64 = 134217728 => "maxStorageBufferBindingSize",
+    min_uniform_buffer_offset_alignment: u32 = 256 => "minUniformBufferOffsetAlignment",
+    min_storage_buffer_offset_alignment: u32 = 256 => "minStorageBufferOffsetAlignment",
+    max_vertex_buffers: u32 = 8 => "maxVertexBuffers",
+    max_buffer_size: u64 = 268435456 => "maxBufferSize",
+    max_vertex_attributes: u32 = 16 => "maxVertexAttributes",
+    max_vertex_buffer_array_stride: u32 = 2048 => "maxVertexBufferArrayStride",
+    max_inter_stage_shader_variables: u32 = 16 => "maxInterStageShaderVariables",
+    max_color_attachments: u32 = 8 => "maxColorAttachments",
+    max_color_attachment_bytes_per_sample: u32 = 32 => "maxColorAttachmentBytesPerSample",
+    max_compute_workgroup_storage_size: u32 = 16384 => "maxComputeWorkgroupStorageSize",
+    max_compute_invocations_per_workgroup: u32 = 256 => "maxComputeInvocationsPerWorkgroup",
+    max_compute_workgroup_size_x: u32 = 256 => "maxComputeWorkgroupSizeX",
+    max_compute_workgroup_size_y: u32 = 256 => "maxComputeWorkgroupSizeY",
+    max_compute_workgroup_size_z: u32 = 64 => "maxComputeWorkgroupSizeZ",
+    max_compute_workgroups_per_dimension: u32 = 65535 => "maxComputeWorkgroupsPerDimension",
+}
+impl Limits {
+    pub fn meets_requirements(&self, required: &Self) -> bool {
+        self.check_requirements(required).is_ok()
+    }
+
+    pub fn non_default_entries(&self) -> Vec<(&'static str, u64)> {
+        let defaults = Self::default();
+
+        self.entries()
+            .into_iter()
+            .filter(|&(name, value)| defaults.get(name) != Some(value))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GPUDeviceDescriptor {
+    pub label: Option<String>,
+    pub required_features: Vec<GPUFeatureName>,
+    pub required_limits: Limits,
+    pub default_queue_label: Option<String>,
+}
+impl GPUDeviceDescriptor {
+    pub fn validate(&self, adapter: &GPUAdapter) -> Result<(), GpuError> {
+        adapter
+            .features()?
+            .check_requirements(&self.required_features.iter().copied().collect())?;
+
+        let supported = adapter.limits_raw();
+        for (name, _) in self.required_limits.non_default_entries() {
+            if Reflect::get(&supported, &name.into())?.is_undefined() {
+                return Err(GpuError::UnknownLimit(name.into()));
+            }
+        }
+
+        Limits::from_supported(&supported).check_requirements(&self.required_limits)
+    }
+
+    pub fn into_object(self) -> Result<Object, GpuError> {
+        let o = Object::new();
+
+        if let Some(l) = self.label {
+            Reflect::set(&o, &"label".into(), &l.into())?;
+        }
+        Reflect::set(
+            &o,
+            &"requiredFeatures".into(),
+            &serde_wasm_bindgen::to_value(&self.required_features)?,
+        )?;
+        let limits = Object::new();
+        // limits left at their defaults are omitted so that implementations unaware of newer limits
+        // still accept the descriptor
+        for (name, value) in self.required_limits.non_default_entries() {
+            Reflect::set(&limits, &name.into(), &(value as f64).into())?;
+        }
+        Reflect::set(&o, &"requiredLimits".into(), &limits)?;
+        if let Some(l) = self.default_queue_label {
+            let default_queue = Object::new();
+            Reflect::set(&default_queue, &"label".into(), &l.into())?;
+            Reflect::set(&o, &"defaultQueue".into(), &default_queue)?;
+        }
+
+        Ok(o)
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    pub type GPUAdapterInfo;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn vendor(this: &GPUAdapterInfo) -> Option<String>;
+    #[wasm_bindgen(method, getter)]
+    pub fn architecture(this: &GPUAdapterInfo) -> Option<String>;
+    #[wasm_bindgen(method, getter)]
+    pub fn device(this: &GPUAdapterInfo) -> Option<String>;
+    #[wasm_bindgen(method, getter)]
+    pub fn description(this: &GPUAdapterInfo) -> Option<String>;
+    #[wasm_bindgen(method, getter, js_name = isFallbackAdapter)]
+    fn is_fallback_adapter_raw(this: &GPUAdapterInfo) -> Option<bool>;
+}
+
+impl GPUAdapter {
+    pub fn is_fallback_adapter(&self) -> bool {
+        // moved from the adapter to GPUAdapterInfo in newer implementations
+        self.is_fallback_adapter_raw()
+            .or_else(|| {
+                let info = self.info_raw();
+                (!info.is_undefined())
+                    .then(|| {
+                        info.unchecked_into::<GPUAdapterInfo>()
+                            .is_fallback_adapter_raw()
+                    })
+                    .flatten()
+            })
+            .unwrap_or(false)
+    }
+
+    pub fn features(&self) -> Result<Features, GpuError> {
+        Features::from_supported(&self.features_raw())
+    }
+
+    pub fn limits(&self) -> Limits {
+        Limits::from_supported(&self.limits_raw())
+    }
+
+    pub async fn info(&self) -> Result<GPUAdapterInfo, GpuError> {
+        let info = self.info_raw();
+        if !info.is_undefined() {
+            return Ok(info.unchecked_into());
+        }
+
+        Ok(self.request_adapter_info_raw().await?.unchecked_into())
+    }
+
+    pub async fn request_device_with_descriptor(
+        &self,
+        descriptor: GPUDeviceDescriptor,
+    ) -> Result<GPUDevice, GpuError> {
+        descriptor.validate(self)?;
+
+        Ok(self
+            .request_device_with_descriptor_raw(&descriptor.into_object()?)
+            .await?
+            .unchecked_into())
+    }
+}
+
+impl GPUCanvasContext {
+    pub fn get_configuration(&self) -> Option<GPUCanvasConfiguration> {
+        let c = self.get_configuration_raw();
+        if c.is_null() || c.is_undefined() {
+            return None;
+        }
+
+        Some(GPUCanvasConfiguration(c.unchecked_into()))
+    }
+}
+
+impl GPU {
+    // navigator.gpu of the current global scope, which is also available in dedicated workers
+    pub fn get() -> Option<GPU> {
+        let navigator = Reflect::get(&js_sys::global(), &"navigator".into()).ok()?;
+        let gpu = Reflect::get(&navigator, &"gpu".into()).ok()?;
+        if gpu.is_null() || gpu.is_undefined() {
+            return None;
+        }
+
+        Some(gpu.unchecked_into())
+    }
+
+    pub async fn request_adapter_with_options(
+        &self,
+        options: GPURequestAdapterOptions,
+    ) -> Result<GPUAdapter, GpuError> {
+        let adapter = self
+            .request_adapter_with_options_raw(&options.into_object()?)
+            .await?;
+        if adapter.is_null() || adapter.is_undefined() {
+            return Err(GpuError::AdapterUnavailable);
+        }
+
+        Ok(adapter.unchecked_into())
+    }
+
+    pub async fn request_default_device(&self) -> Result<GPUDevice, GpuError> {
+        let adapter = self.request_adapter().await;
+        if adapter.is_null() || adapter.is_undefined() {
+            return Err(GpuError::AdapterUnavailable);
+        }
+
+        Ok(adapter
+            .unchecked_into::<GPUAdapter>()
+            .request_device()
+            .await?
+            .unchecked_into())
+    }
+}
+
+impl GPUDevice {
+    pub fn features(&self) -> Result<Features, GpuError> {
+        Features::from_supported(&self.features_raw())
+    }
+
+    pub fn limits(&self) -> Limits {
+        Limits::from_supported(&self.limits_raw())
+    }
+
+    pub async fn lost(&self) -> GPUDeviceLostInfo {
+        // the lost promise never rejects
+        wasm_bindgen_futures::JsFuture::from(self.lost_promise())
+            .await
+            .unwrap_or_else(|e| e)
+            .unchecked_into()
+    }
+}
+
+// callbacks may register further callbacks, so the list must not stay borrowed while they run
+fn run_callbacks<C>(callbacks: &RefCell<Vec<C>>, mut f: impl FnMut(&mut C)) {
+    let mut running = std::mem::take(&mut *callbacks.borrow_mut());
+    for callback in running.iter_mut() {
+        f(callback);
+    }
+
+    let mut callbacks = callbacks.borrow_mut();
+    running.append(&mut callbacks);
+    *callbacks = running;
+}
+
+type DeviceCreatedCallback = Box<dyn FnMut(&GPUDevice) -> Result<(), GpuError>>;
+type DeviceLostCallback = Box<dyn FnMut(&GPUDeviceLostInfo)>;
+
+const DEVICE_RECOVERY_ATTEMPTS: u32 = 6;
+/// doubled after every failed attempt
+const DEVICE_RECOVERY_BACKOFF_MS: i32 = 250;
+
+type DeviceRequest = Box<
+    dyn Fn() -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<GPUDevice, GpuError>>>>,
+>;
+
+struct DeviceRecoveryState {
+    request_device: DeviceRequest,
+    device: RefCell<GPUDevice>,
+    on_created: RefCell<Vec<DeviceCreatedCallback>>,
+    on_lost: RefCell<Vec<DeviceLostCallback>>,
+}
+
+pub struct DeviceRecovery(Rc<DeviceRecoveryState>);
+impl DeviceRecovery {
+    /// `request_device` is called again after every loss, so it should request the adapter and
+    /// device with the same options and descriptor each time
+    pub async fn new<F>(request_device: impl Fn() -> F + 'static) -> Result<Self, GpuError>
+    where
+        F: std::future::Future<Output = Result<GPUDevice, GpuError>> + 'static,
+    {
+        let request_device: DeviceRequest = Box::new(move || Box::pin(request_device()));
+        let device = request_device().await?;
+        let state = Rc::new(DeviceRecoveryState {
+            request_device,
+            device: RefCell::new(device),
+            on_created: RefCell::new(Vec::new()),
+            on_lost: RefCell::new(Vec::new()),
+        });
+        wasm_bindgen_futures::spawn_local(Self::watch(Rc::downgrade(&state)));
+
+        Ok(Self(state))
+    }
+
+    pub fn device(&self) -> GPUDevice {
+        JsValue::clone(&self.0.device.borrow()).unchecked_into()
+    }
+
+    pub fn on_device_created(
+        &self,
+        mut callback: impl FnMut(&GPUDevice) -> Result<(), GpuError> + 'static,
+    ) -> Result<(), GpuError> {
+        callback(&self.device())?;
+        self.0.on_created.borrow_mut().push(Box::new(callback));
+
+        Ok(())
+    }
+
+    pub fn on_device_lost(&self, callback: impl FnMut(&GPUDeviceLostInfo) + 'static) {
+        self.0.on_lost.borrow_mut().push(Box::new(callback));
+    }
+
+    async fn watch(state: Weak<DeviceRecoveryState>) {
+        loop {
+            let Some(device) = state
+                .upgrade()
+                .map(|s| JsValue::clone(&s.device.borrow()).unchecked_into::<GPUDevice>())
+            else {
+                return;
+            };
+            let info = device.lost().await;
+            let Some(s) = state.upgrade() else {
+                return;
+            };
+            run_callbacks(&s.on_lost, |callback| callback(&info));
+            if info.reason() == GPUDeviceLostReason::Destroyed {
+                // destroyed explicitly by the application
+                return;
+            }
+            drop(s);
+
+            let Some(device) = Self::recover(&state).await else {
+                return;
+            };
+            let Some(s) = state.upgrade() else {
+                return;
+            };
+            *s.device.borrow_mut() = JsValue::clone(&device).unchecked_into();
+            run_callbacks(&s.on_created, |callback| {
+                if let Err(e) = callback(&device) {
+                    log(&format!("failed to recreate resources: {e}"));
+                }
+            });
+        }
+    }
+
+    // requesting a device can fail for a while after a GPU reset or while the tab is in the
+    // background, so give up only after several attempts
+    async fn recover(state: &Weak<DeviceRecoveryState>) -> Option<GPUDevice> {
+        for attempt in 0..DEVICE_RECOVERY_ATTEMPTS {
+            if attempt > 0 {
+                sleep(DEVICE_RECOVERY_BACKOFF_MS << (attempt - 1)).await;
+            }
+            let request = (state.upgrade()?.request_device)();
+            match request.await {
+                Ok(device) => return Some(device),
+                Err(e) => log(&format!(
+                    "failed to recover lost device (attempt {}): {e}",
+                    attempt + 1
+                )),
+            }
+        }
+        log(&format!(
+            "giving up on recovering the lost device after {DEVICE_RECOVERY_ATTEMPTS} attempts"
+        ));
+
+        None
+    }
+}
+
+pub struct UncapturedErrorSubscription {
+    device: GPUDevice,
+    listener: Option<Closure<dyn FnMut(JsValue)>>,
+}
+impl UncapturedErrorSubscription {
+    pub fn forget(mut self) {
+        if let Some(listener) = self.listener.take() {
+            listener.forget();
+        }
+    }
+}
+impl Drop for UncapturedErrorSubscription {
+    fn drop(&mut self) {
+        if let Some(listener) = &self.listener {
+            self.device
+                .remove_event_listener("uncapturederror", listener.as_ref().unchecked_ref());
+        }
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = requestAnimationFrame)]
+    fn request_animation_frame(callback: &js_sys::Function) -> i32;
+    #[wasm_bindgen(js_name = cancelAnimationFrame)]
+    fn cancel_animation_frame(handle: i32);
+    #[wasm_bindgen(js_name = clearTimeout)]
+    fn clear_timeout(handle: i32);
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn performance_now() -> f64;
+}
+
+/// used where requestAnimationFrame is missing, e.g. in some worker implementations
+const FALLBACK_FRAME_INTERVAL_MS: i32 = 16;
+
+pub struct Frame {
+    pub texture: GPUTexture,
+    /// milliseconds, as passed to the requestAnimationFrame callback (or performance.now() when
+    /// frames are scheduled with setTimeout)
+    pub timestamp: f64,
+    /// milliseconds since the previous frame (0 on the first frame after start/resume)
+    pub delta: f64,
+}
+
+type FrameCallback = Box<dyn FnMut(&Frame) -> Result<(), GpuError>>;
+
+struct FrameLoopState {
+    context: GPUCanvasContext,
+    callback: RefCell<FrameCallback>,
+    tick: Closure<dyn FnMut(JsValue)>,
+    animation_frames: bool,
+    running: Cell<bool>,
+    handle: Cell<Option<i32>>,
+    last_timestamp: Cell<Option<f64>>,
+}
+impl FrameLoopState {
+    fn schedule(&self) {
+        if self.handle.get().is_none() {
+            let tick = self.tick.as_ref().unchecked_ref();
+            self.handle.set(Some(if self.animation_frames {
+                request_animation_frame(tick)
+            } else {
+                set_timeout(tick, FALLBACK_FRAME_INTERVAL_MS)
+            }));
+        }
+    }
+
+    fn cancel(&self) {
+        match self.handle.take() {
+            Some(h) if self.animation_frames => cancel_animation_frame(h),
+            Some(h) => clear_timeout(h),
+            None => (),
+        }
+    }
+
+    fn tick(&self, timestamp: f64) {
+        self.handle.set(None);
+        if !self.running.get() {
+            return;
+        }
+
+        let delta = self
+            .last_timestamp
+            .replace(Some(timestamp))
+            .map_or(0.0, |t| timestamp - t);
+        let result = self.context.get_current_texture().and_then(|texture| {
+            (self.callback.borrow_mut())(&Frame {
+                texture,
+                timestamp,
+                delta,
+            })
+        });
+        if let Err(e) = result {
+            log(&format!("frame failed: {e}"));
+        }
+
+        if self.running.get() {
+            self.schedule();
+        }
+    }
+}
+
+pub struct FrameLoop(Rc<FrameLoopState>);
+impl FrameLoop {
+    pub fn new(
+        context: GPUCanvasContext,
+        callback: impl FnMut(&Frame) -> Result<(), GpuError> + 'static,
+    ) -> Self {
+        Self(Rc::new_cyclic(|state: &Weak<FrameLoopState>| {
+            let state = state.clone();
+
+            FrameLoopState {
+                context,
+                callback: RefCell::new(Box::new(callback)),
+                tick: Closure::new(move |timestamp: JsValue| {
+                    if let Some(s) = state.upgrade() {
+                        // setTimeout callbacks get no timestamp
+                        s.tick(timestamp.as_f64().unwrap_or_else(performance_now));
+                    }
+                }),
+                animation_frames: Reflect::has(&js_sys::global(), &"requestAnimationFrame".into())
+                    .unwrap_or(false),
+                running: Cell::new(false),
+                handle: Cell::new(None),
+                last_timestamp: Cell::new(None),
+            }
+        }))
+    }
+
+    pub fn start(&self) {
+        if !self.0.running.replace(true) {
+            self.0.last_timestamp.set(None);
+            self.0.schedule();
+        }
+    }
+
+    pub fn pause(&self) {
+        self.0.running.set(false);
+        self.0.cancel();
+    }
+
+    pub fn resume(&self) {
+        self.start();
+    }
+
+    pub fn stop(self) {
+        self.pause();
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.0.running.get()
+    }
+}
+impl Drop for FrameLoop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    type ResizeObserver;
+
+    #[wasm_bindgen(constructor)]
+    fn new(callback: &js_sys::Function) -> ResizeObserver;
+    #[wasm_bindgen(method, catch)]
+    fn observe(this: &ResizeObserver, target: &JsValue, options: &Object) -> Result<(), JsValue>;
+    #[wasm_bindgen(method)]
+    fn disconnect(this: &ResizeObserver);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SurfaceDescriptor {
+    pub format: GPUTextureFormat,
+    pub sample_count: u32,
+    pub depth_format: Option<GPUTextureFormat>,
+}
+
+struct SurfaceTargets {
+    width: u32,
+    height: u32,
+    msaa: Option<MultisampleColorTarget>,
+    depth: Option<GPUTextureView>,
+}
+impl SurfaceTargets {
+    // textures can be neither empty nor larger than the device allows, while a canvas can be both
+    fn clamp_size(width: u32, height: u32, max_dimension: u32) -> (u32, u32) {
+        (
+            width.clamp(1, max_dimension),
+            height.clamp(1, max_dimension),
+        )
+    }
+
+    fn new(
+        device: &GPUDevice,
+        width: u32,
+        height: u32,
+        descriptor: &SurfaceDescriptor,
+    ) -> Result<Self, GpuError> {
+        let size = GPUExtent3D::new_2d(width, height);
+        let msaa = if descriptor.sample_count > 1 {
+            Some(MultisampleColorTarget::new(
+                device,
+                size,
+                descriptor.format,
+                descriptor.sample_count,
+            )?)
+        } else {
+            None
+        };
+        let depth = match descriptor.depth_format {
+            Some(f) => Some(
+                device
+                    .create_texture(
+                        GPUTextureDescriptor::new(size, f, TextureUsages::RENDER_ATTACHMENT)?
+                            .label("Depth Target")?
+                            .sample_count(descriptor.sample_count)?,
+                    )?
+                    .create_view()?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            width,
+            height,
+            msaa,
+            depth,
+        })
+    }
+}
+
+type SurfaceResizeCallback = Box<dyn FnMut(u32, u32)>;
+type SurfaceObserverCallback = Closure<dyn FnMut(Array)>;
+
+trait SurfaceCanvas {
+    fn width(&self) -> u32;
+    fn height(&self) -> u32;
+    fn set_width(&self, width: u32);
+    fn set_height(&self, height: u32);
+}
+impl SurfaceCanvas for HTMLCanvasElement {
+    fn width(&self) -> u32 {
+        HTMLCanvasElement::width(self)
+    }
+
+    fn height(&self) -> u32 {
+        HTMLCanvasElement::height(self)
+    }
+
+    fn set_width(&self, width: u32) {
+        HTMLCanvasElement::set_width(self, width)
+    }
+
+    fn set_height(&self, height: u32) {
+        HTMLCanvasElement::set_height(self, height)
+    }
+}
+impl SurfaceCanvas for OffscreenCanvas {
+    fn width(&self) -> u32 {
+        OffscreenCanvas::width(self)
+    }
+
+    fn height(&self) -> u32 {
+        OffscreenCanvas::height(self)
+    }
+
+    fn set_width(&self, width: u32) {
+        OffscreenCanvas::set_width(self, width)
+    }
+
+    fn set_height(&self, height: u32) {
+        OffscreenCanvas::set_height(self, height)
+    }
+}
+
+struct SurfaceState {
+    canvas: Box<dyn SurfaceCanvas>,
+    context: GPUCanvasContext,
+    device: GPUDevice,
+    descriptor: SurfaceDescriptor,
+    max_dimension: u32,
+    targets: RefCell<SurfaceTargets>,
+    on_resize: RefCell<Vec<SurfaceResizeCallback>>,
+    observer: Option<(ResizeObserver, SurfaceObserverCallback)>,
+}
+impl Drop for SurfaceState {
+    fn drop(&mut self) {
+        if let Some((observer, _)) = &self.observer {
+            observer.disconnect();
+        }
+    }
+}
+impl SurfaceState {
+    fn observed_size(entry: &JsValue) -> Option<(u32, u32)> {
+        let first_size = |key: &str| {
+            Reflect::get(entry, &key.into())
+                .ok()
+                .filter(|x| !x.is_undefined())
+                .and_then(|x| Reflect::get_u32(&x, 0).ok())
+                .filter(|x| !x.is_undefined())
+        };
+        let dimensions = |size: &JsValue| {
+            Some((
+                Reflect::get(size, &"inlineSize".into()).ok()?.as_f64()?,
+                Reflect::get(size, &"blockSize".into()).ok()?.as_f64()?,
+            ))
+        };
+
+        // devicePixelContentBoxSize is exact, contentBoxSize needs to be scaled by devicePixelRatio
+        if let Some((w, h)) = first_size("devicePixelContentBoxSize").and_then(|x| dimensions(&x)) {
+            return Some((w as u32, h as u32));
+        }
+        let dpr = Reflect::get(&js_sys::global(), &"devicePixelRatio".into())
+            .ok()
+            .and_then(|x| x.as_f64())
+            .unwrap_or(1.0);
+        let (w, h) = first_size("contentBoxSize").and_then(|x| dimensions(&x))?;
+
+        Some(((w * dpr).round() as u32, (h * dpr).round() as u32))
+    }
+
+    fn resize(&self, width: u32, height: u32) -> Result<(), GpuError> {
+        let (width, height) = SurfaceTargets::clamp_size(width, height, self.max_dimension);
+        {
+            let targets = self.targets.borrow();
+            if targets.width == width && targets.height == height {
+                return Ok(());
+            }
+        }
+
+        self.canvas.set_width(width);
+        self.canvas.set_height(height);
+        *self.targets.borrow_mut() =
+            SurfaceTargets::new(&self.device, width, height, &self.descriptor)?;
+        run_callbacks(&self.on_resize, |callback| callback(width, height));
+
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct Surface(Rc<SurfaceState>);
+impl Surface {
+    pub fn new(
+        device: &GPUDevice,
+        canvas: &HTMLCanvasElement,
+        context: &GPUCanvasContext,
+        descriptor: SurfaceDescriptor,
+    ) -> Result<Self, GpuError> {
+        let surface = Self::with_canvas(
+            device,
+            Box::new(JsValue::clone(canvas).unchecked_into::<HTMLCanvasElement>()),
+            context,
+            descriptor,
+            true,
+        )?;
+
+        if let Some((observer, _)) = &surface.0.observer {
+            let options = Object::new();
+            Reflect::set(&options, &"box".into(), &"device-pixel-content-box".into())?;
+            if observer.observe(canvas, &options).is_err() {
+                // device-pixel-content-box is not supported everywhere
+                Reflect::set(&options, &"box".into(), &"content-box".into())?;
+                observer.observe(canvas, &options)?;
+            }
+        }
+
+        Ok(surface)
+    }
+
+    // ResizeObserver is not available in workers: the owner of the canvas element has to forward
+    // size changes through `resize`
+    pub fn new_offscreen(
+        device: &GPUDevice,
+        canvas: &OffscreenCanvas,
+        context: &GPUCanvasContext,
+        descriptor: SurfaceDescriptor,
+    ) -> Result<Self, GpuError> {
+        Self::with_canvas(
+            device,
+            Box::new(JsValue::clone(canvas).unchecked_into::<OffscreenCanvas>()),
+            context,
+            descriptor,
+            false,
+        )
+    }
+
+    fn with_canvas(
+        device: &GPUDevice,
+        canvas: Box<dyn SurfaceCanvas>,
+        context: &GPUCanvasContext,
+        descriptor: SurfaceDescriptor,
+        observe_resize: bool,
+    ) -> Result<Self, GpuError> {
+        let max_dimension = device.limits().max_texture_dimension_2d;
+        let (width, height) =
+            SurfaceTargets::clamp_size(canvas.width(), canvas.height(), max_dimension);
+        if (width, height) != (canvas.width(), canvas.height()) {
+            canvas.set_width(width);
+            canvas.set_height(height);
+        }
+        let targets = SurfaceTargets::new(device, width, height, &descriptor)?;
+        let state = Rc::new_cyclic(|state: &Weak<SurfaceState>| {
+            let state = state.clone();
+            let observer = observe_resize.then(|| {
+                let callback = SurfaceObserverCallback::new(move |entries: Array| {
+                    let Some(s) = state.upgrade() else {
+                        return;
+                    };
+                    // only the last entry matters when several changes were batched
+                    let Some((w, h)) = SurfaceState::observed_size(&entries.pop()) else {
+                        return;
+                    };
+                    if let Err(e) = s.resize(w, h) {
+                        log(&format!("failed to resize surface: {e}"));
+                    }
+                });
+
+                (
+                    ResizeObserver::new(callback.as_ref().unchecked_ref()),
+                    callback,
+                )
+            });
+
+            SurfaceState {
+                canvas,
+                context: JsValue::clone(context).unchecked_into(),
+                device: JsValue::clone(device).unchecked_into(),
+                descriptor,
+                max_dimension,
+                targets: RefCell::new(targets),
+                on_resize: RefCell::new(Vec::new()),
+                observer,
+            }
+        });
+
+        Ok(Self(state))
+    }
+
+    pub fn context(&self) -> &GPUCanvasContext {
+        &self.0.context
+    }
+
+    pub fn configure(&self, configuration: GPUCanvasConfiguration) -> Result<(), GpuError> {
+        self.0.context.configure(configuration)
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        let targets = self.0.targets.borrow();
+
+        (targets.width, targets.height)
+    }
 
-    #[wasm_bindgen(typescript_type = "GPUBufferUsage")]
-    type GPUBufferUsage;
+    pub fn sample_count(&self) -> u32 {
+        self.0.descriptor.sample_count
+    }
 
-    #[wasm_bindgen(js_namespace = navigator, js_name = gpu)]
-    static NAVIGATOR_GPU: GPU;
+    pub fn multisample_state(&self) -> GPUMultisampleState {
+        GPUMultisampleState {
+            count: self.0.descriptor.sample_count,
+            ..Default::default()
+        }
+    }
 
-    #[wasm_bindgen(method, js_name = requestAdapter)]
-    async fn request_adapter(this: &GPU) -> JsValue;
-    #[wasm_bindgen(method, js_name = getPreferredCanvasFormat)]
-    pub fn get_preferred_canvas_format(gpu: &GPU) -> String;
+    pub fn resize(&self, width: u32, height: u32) -> Result<(), GpuError> {
+        self.0.resize(width, height)
+    }
 
-    #[wasm_bindgen(method, getter)]
-    fn features(this: &GPUAdapter) -> GPUSupportedFeatures;
-    #[wasm_bindgen(method, js_name = requestDevice)]
-    async fn request_device(this: &GPUAdapter) -> JsValue;
-    #[wasm_bindgen(method, getter)]
-    pub fn queue(this: &GPUDevice) -> GPUQueue;
+    pub fn on_resize(&self, callback: impl FnMut(u32, u32) + 'static) {
+        self.0.on_resize.borrow_mut().push(Box::new(callback));
+    }
 
-    #[wasm_bindgen(method, js_name = createBuffer, catch)]
-    fn create_buffer(
-        this: &GPUDevice,
-        descriptor: GPUBufferCreateDescription,
-    ) -> Result<GPUBuffer, JsValue>;
-    #[wasm_bindgen(method, js_name = createCommandEncoder, catch)]
-    pub fn create_command_encoder(this: &GPUDevice) -> Result<GPUCommandEncoder, JsValue>;
-    #[wasm_bindgen(method, js_name = createCommandEncoder, catch)]
-    pub fn create_command_encoder_with_description(
-        this: &GPUDevice,
-        descriptor: GPUCommandEncoderCreateDescription,
-    ) -> Result<GPUCommandEncoder, JsValue>;
-    #[wasm_bindgen(method, js_name = createShaderModule, catch)]
-    pub fn create_shader_module(
-        device: &GPUDevice,
-        descriptor: GPUShaderModuleDescriptor,
-    ) -> Result<GPUShaderModule, JsValue>;
-    #[wasm_bindgen(method, js_name = createPipelineLayout, catch)]
-    pub fn create_pipeline_layout(
-        device: &GPUDevice,
-        descriptor: GPUPipelineLayoutDescriptor,
-    ) -> Result<GPUPipelineLayout, JsValue>;
-    #[wasm_bindgen(method, js_name = createRenderPipeline, catch)]
-    pub fn create_render_pipeline(
-        device: &GPUDevice,
-        descriptor: GPURenderPipelineDescriptor,
-    ) -> Result<GPURenderPipeline, JsValue>;
-    #[wasm_bindgen(method, js_name = createRenderBundleEncoder, catch)]
-    pub fn create_render_bundle_encoder(
-        device: &GPUDevice,
-        descriptor: GPURenderBundleEncoderDescriptor,
-    ) -> Result<GPURenderBundleEncoder, JsValue>;
-}
+    pub fn color_attachment(
+        &self,
+        view: &GPUTextureView,
+    ) -> Result<GPURenderPassColorAttachment, GpuError> {
+        match &self.0.targets.borrow().msaa {
+            Some(msaa) => msaa.color_attachment(view),
+            None => GPURenderPassColorAttachment::new(view),
+        }
+    }
 
-#[wasm_bindgen]
-extern "C" {
-    pub type GPUBuffer;
+    pub fn depth_stencil_attachment(
+        &self,
+    ) -> Result<Option<GPURenderPassDepthStencilAttachment>, GpuError> {
+        let (Some(view), Some(format)) = (
+            &self.0.targets.borrow().depth,
+            self.0.descriptor.depth_format,
+        ) else {
+            return Ok(None);
+        };
 
-    #[wasm_bindgen(method)]
-    pub fn destroy(this: &GPUBuffer);
-    #[wasm_bindgen(method, js_name = mapAsync, catch)]
-    pub async fn map_async(this: &GPUBuffer, mode: u32) -> Result<JsValue, JsValue>;
-    #[wasm_bindgen(method, js_name = mapAsync, catch)]
-    pub async fn map_range_async(
-        this: &GPUBuffer,
-        mode: u32,
-        offset: usize,
-        size: usize,
-    ) -> Result<JsValue, JsValue>;
-    #[wasm_bindgen(method)]
-    pub fn unmap(this: &GPUBuffer);
-    #[wasm_bindgen(method, js_name = getMappedRange, catch)]
-    pub fn get_mapped_range(
-        this: &GPUBuffer,
-        offset: usize,
-        size: usize,
-    ) -> Result<ArrayBuffer, JsValue>;
-    #[wasm_bindgen(method, js_name = getMappedRange, catch)]
-    pub fn get_mapped_range_from(this: &GPUBuffer, offset: usize) -> Result<ArrayBuffer, JsValue>;
-    #[wasm_bindgen(method, js_name = getMappedRange, catch)]
-    pub fn get_mapped_range_full(this: &GPUBuffer) -> Result<ArrayBuffer, JsValue>;
+        let attachment = GPURenderPassDepthStencilAttachment::new(view)?
+            .depth_ops(GPULoadOp::Clear, GPUStoreOp::Discard)?
+            .depth_clear_value(1.0)?;
+        if format.has_stencil() {
+            return attachment
+                .stencil_ops(GPULoadOp::Clear, GPUStoreOp::Discard)
+                .map(Some);
+        }
 
-    #[wasm_bindgen(method, getter)]
-    pub fn usage(this: &GPUBuffer) -> u32;
-    #[wasm_bindgen(method, getter)]
-    pub fn size(this: &GPUBuffer) -> usize;
+        Ok(Some(attachment))
+    }
 }
 
 #[wasm_bindgen]
-extern "C" {
-    pub type GPUTexture;
-    pub type GPUTextureView;
-
-    #[wasm_bindgen(method)]
-    pub fn destroy(texture: &GPUTexture);
-    #[wasm_bindgen(method, js_name = createView, catch)]
-    pub fn create_view(texture: &GPUTexture) -> Result<GPUTextureView, JsValue>;
+pub struct SurfaceHandle(Surface);
+#[wasm_bindgen]
+impl SurfaceHandle {
+    pub fn resize(&self, width: u32, height: u32) -> Result<(), GpuError> {
+        self.0.resize(width, height)
+    }
 }
 
-#[wasm_bindgen]
-extern "C" {
-    pub type GPUCommandEncoder;
-    pub type GPUCommandBuffer;
+impl GPUCommandEncoder {
+    pub fn copy_buffer_to_texture(
+        &self,
+        source: GPUImageCopyBuffer,
+        destination: GPUImageCopyTexture,
+        copy_size: GPUExtent3D,
+    ) -> Result<(), GpuError> {
+        self.copy_buffer_to_texture_raw(
+            &source.into_object()?,
+            &destination.into_object()?,
+            &copy_size.into_object()?,
+        )
+    }
 
-    #[wasm_bindgen(method, catch)]
-    pub fn finish(this: &GPUCommandEncoder) -> Result<GPUCommandBuffer, JsValue>;
-    #[wasm_bindgen(method, catch)]
-    pub fn finish_with_descriptor(
-        this: &GPUCommandEncoder,
-        descriptor: GPUCommandBufferDescriptor,
-    ) -> Result<GPUCommandBuffer, JsValue>;
+    pub fn copy_texture_to_buffer(
+        &self,
+        source: GPUImageCopyTexture,
+        destination: GPUImageCopyBuffer,
+        copy_size: GPUExtent3D,
+    ) -> Result<(), GpuError> {
+        self.copy_texture_to_buffer_raw(
+            &source.into_object()?,
+            &destination.into_object()?,
+            &copy_size.into_object()?,
+        )
+    }
 
-    #[wasm_bindgen(method, js_name = copyBufferToBuffer, catch)]
-    pub fn copy_buffer_to_buffer(
-        this: &GPUCommandEncoder,
-        source: &GPUBuffer,
-        source_offset: usize,
-        destination: &GPUBuffer,
-        destination_offset: usize,
-        size: usize,
-    ) -> Result<(), JsValue>;
+    pub fn copy_texture_to_texture(
+        &self,
+        source: GPUImageCopyTexture,
+        destination: GPUImageCopyTexture,
+        copy_size: GPUExtent3D,
+    ) -> Result<(), GpuError> {
+        self.copy_texture_to_texture_raw(
+            &source.into_object()?,
+            &destination.into_object()?,
+            &copy_size.into_object()?,
+        )
+    }
 
-    #[wasm_bindgen(method, js_name = beginRenderPass, catch)]
     pub fn begin_render_pass(
-        this: &GPUCommandEncoder,
+        &self,
         descriptor: GPURenderPassDescriptor,
-    ) -> Result<GPURenderPassEncoder, JsValue>;
-}
-
-#[wasm_bindgen]
-extern "C" {
-    pub type GPUQueue;
+    ) -> Result<GPURenderPassEncoder, GpuError> {
+        self.begin_render_pass_raw(&descriptor.into_object()?)
+    }
 
-    #[wasm_bindgen(method, catch)]
-    pub fn submit(queue: &GPUQueue, command_buffers: Vec<GPUCommandBuffer>) -> Result<(), JsValue>;
+    pub fn begin_compute_pass_with_descriptor(
+        &self,
+        descriptor: GPUComputePassDescriptor,
+    ) -> Result<GPUComputePassEncoder, GpuError> {
+        self.begin_compute_pass_with_descriptor_raw(&descriptor.into_object()?)
+    }
 }
 
-#[wasm_bindgen]
-extern "C" {
-    pub type GPURenderPassEncoder;
+impl GPU {
+    pub fn get_preferred_canvas_format(&self) -> Result<GPUTextureFormat, GpuError> {
+        Ok(self.get_preferred_canvas_format_string().parse()?)
+    }
+}
 
-    #[wasm_bindgen(method, catch)]
-    pub fn end(encoder: &GPURenderPassEncoder) -> Result<(), JsValue>;
-    #[wasm_bindgen(method, catch, js_name = setPipeline)]
-    pub fn set_pipeline(
-        encoder: &GPURenderPassEncoder,
-        pipeline: &GPURenderPipeline,
-    ) -> Result<(), JsValue>;
-    #[wasm_bindgen(method, catch, js_name = setScissorRect)]
-    pub fn set_scissor_rect(
-        encoder: &GPURenderPassEncoder,
-        x: u32,
-        y: u32,
-        width: u32,
-        height: u32,
-    ) -> Result<(), JsValue>;
-    #[wasm_bindgen(method, catch, js_name = setViewport)]
-    pub fn set_viewport(
-        encoder: &GPURenderPassEncoder,
-        x: f32,
-        y: f32,
-        width: f32,
-        height: f32,
-        min_depth: f32,
-        max_depth: f32,
-    ) -> Result<(), JsValue>;
-    #[wasm_bindgen(method, catch, js_name = setVertexBuffer)]
-    pub fn set_vertex_buffer(
-        encoder: &GPURenderPassEncoder,
-        slot: u32,
-        buffer: &GPUBuffer,
-        offset: usize,
-        size: usize,
-    ) -> Result<(), JsValue>;
-    #[wasm_bindgen(method)]
-    pub fn draw(
-        encoder: &GPURenderPassEncoder,
-        vertex_count: u32,
-        instance_count: u32,
-        first_vertex: u32,
-        first_instance: u32,
-    );
-    #[wasm_bindgen(method, js_name = executeBundles, catch)]
-    pub fn execute_bundles(
-        encoder: &GPURenderPassEncoder,
-        bundles: Vec<GPURenderBundle>,
-    ) -> Result<(), JsValue>;
+impl GPUTexture {
+    pub fn format(&self) -> Option<GPUTextureFormat> {
+        self.format_string().parse().ok()
+    }
 }
 
-#[wasm_bindgen]
-extern "C" {
-    pub type GPURenderBundleEncoder;
-    pub type GPURenderBundle;
+impl GPURenderPassEncoder {
+    pub fn set_bind_group_with_dynamic_offsets(
+        &self,
+        index: u32,
+        bind_group: &GPUBindGroup,
+        dynamic_offsets: &[u32],
+    ) -> Result<(), GpuError> {
+        self.set_bind_group_with_dynamic_offsets_data(
+            index,
+            bind_group,
+            dynamic_offsets,
+            0,
+            dynamic_offsets.len() as _,
+        )
+    }
+}
 
-    #[wasm_bindgen(method, catch)]
-    pub fn finish(encoder: &GPURenderBundleEncoder) -> Result<GPURenderBundle, JsValue>;
-    #[wasm_bindgen(method, catch, js_name = setPipeline)]
-    pub fn set_pipeline(
-        encoder: &GPURenderBundleEncoder,
-        pipeline: &GPURenderPipeline,
-    ) -> Result<(), JsValue>;
-    #[wasm_bindgen(method, catch, js_name = setVertexBuffer)]
-    pub fn set_vertex_buffer(
-        encoder: &GPURenderBundleEncoder,
-        slot: u32,
-        buffer: &GPUBuffer,
-        offset: usize,
-        size: usize,
-    ) -> Result<(), JsValue>;
-    #[wasm_bindgen(method)]
-    pub fn draw(
-        encoder: &GPURenderBundleEncoder,
-        vertex_count: u32,
-        instance_count: u32,
-        first_vertex: u32,
-        first_instance: u32,
-    );
+impl GPUComputePassEncoder {
+    pub fn set_bind_group_with_dynamic_offsets(
+        &self,
+        index: u32,
+        bind_group: &GPUBindGroup,
+        dynamic_offsets: &[u32],
+    ) -> Result<(), GpuError> {
+        self.set_bind_group_with_dynamic_offsets_data(
+            index,
+            bind_group,
+            dynamic_offsets,
+            0,
+            dynamic_offsets.len() as _,
+        )
+    }
 }
 
-#[allow(non_snake_case)]
-#[wasm_bindgen(js_namespace = GPUBufferUsage)]
-extern "C" {
-    #[wasm_bindgen]
-    static COPY_SRC: u32;
-    #[wasm_bindgen]
-    static COPY_DST: u32;
-    #[wasm_bindgen]
-    static INDEX: u32;
-    #[wasm_bindgen]
-    static INDIRECT: u32;
-    #[wasm_bindgen]
-    static MAP_READ: u32;
-    #[wasm_bindgen]
-    static MAP_WRITE: u32;
-    #[wasm_bindgen]
-    static QUERY_RESOLVE: u32;
-    #[wasm_bindgen]
-    static STORAGE: u32;
-    #[wasm_bindgen]
-    static UNIFORM: u32;
-    #[wasm_bindgen]
-    static VERTEX: u32;
+impl GPURenderBundleEncoder {
+    pub fn set_bind_group_with_dynamic_offsets(
+        &self,
+        index: u32,
+        bind_group: &GPUBindGroup,
+        dynamic_offsets: &[u32],
+    ) -> Result<(), GpuError> {
+        self.set_bind_group_with_dynamic_offsets_data(
+            index,
+            bind_group,
+            dynamic_offsets,
+            0,
+            dynamic_offsets.len() as _,
+        )
+    }
 }
 
 const SHADER: &str = r#"
@@ -639,75 +4564,106 @@ fn fsh(v: VertexOutput) -> @location(0) vec4f {
 "#;
 
 #[wasm_bindgen]
-pub async fn start(render_target_element: &HTMLCanvasElement) {
+pub async fn start(render_target_element: &HTMLCanvasElement) -> Result<(), GpuError> {
+    run(
+        render_target_element.get_context("webgpu"),
+        |device, ctx, descriptor| Surface::new(device, render_target_element, ctx, descriptor),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub async fn start_offscreen(canvas: &OffscreenCanvas) -> Result<SurfaceHandle, GpuError> {
+    run(canvas.get_context("webgpu"), |device, ctx, descriptor| {
+        Surface::new_offscreen(device, canvas, ctx, descriptor)
+    })
+    .await
+    .map(SurfaceHandle)
+}
+
+async fn run(
+    ctx: JsValue,
+    create_surface: impl FnOnce(
+        &GPUDevice,
+        &GPUCanvasContext,
+        SurfaceDescriptor,
+    ) -> Result<Surface, GpuError>,
+) -> Result<Surface, GpuError> {
     #[cfg(feature = "panic_hook")]
     std::panic::set_hook(Box::new(console_error_panic_hook::hook));
 
     log("hello from wasm");
 
-    let gpu = &*NAVIGATOR_GPU;
-    if gpu.is_null() {
+    let Some(gpu) = GPU::get() else {
         panic!("no webgpu available");
-    }
-    let adapter = gpu.request_adapter().await.unchecked_into::<GPUAdapter>();
-    if adapter.is_null() {
-        panic!("no adapters available");
-    }
+    };
+    let adapter = gpu
+        .request_adapter_with_options(GPURequestAdapterOptions {
+            power_preference: Some(GPUPowerPreference::LowPower),
+            ..Default::default()
+        })
+        .await
+        .expect("no adapters available");
 
     log("webgpu is available on this platform");
+    // adapter info is only diagnostic output, so the demo keeps going without it
+    match adapter.info().await {
+        Ok(info) => log(&format!(
+            "adapter: vendor={} architecture={} device={} description={} fallback={}",
+            info.vendor().unwrap_or_default(),
+            info.architecture().unwrap_or_default(),
+            info.device().unwrap_or_default(),
+            info.description().unwrap_or_default(),
+            adapter.is_fallback_adapter()
+        )),
+        Err(e) => log(&format!("failed to get adapter info: {e}")),
+    }
 
-    let features = adapter.features();
-    let features_iter = js_sys::try_iter(&features)
-        .expect("invalid")
-        .expect("not iterable");
-    for x in features_iter {
+    let features = adapter
+        .features()
+        .expect("Failed to enumerate adapter features");
+    for x in features.iter() {
         log(&format!("feature: {x:?}"));
     }
+    log(&format!("limits: {:?}", adapter.limits()));
 
-    let ctx: GPUCanvasContext = render_target_element
-        .get_context("webgpu")
-        .try_into()
+    let ctx = ctx
+        .dyn_into::<GPUCanvasContext>()
         .expect("not a canvas context");
 
-    let device = adapter.request_device().await.unchecked_into::<GPUDevice>();
-    let format = gpu.get_preferred_canvas_format();
-    ctx.configure(GPUCanvasConfiguration::new(&device).with_format(&format));
-    log(&format!("canvas was configured with format {format}"));
+    let device = adapter
+        .request_device_with_descriptor(GPUDeviceDescriptor {
+            label: Some("main device".into()),
+            default_queue_label: Some("main queue".into()),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to request device");
+    wasm_bindgen_futures::spawn_local({
+        let device = JsValue::clone(&device).unchecked_into::<GPUDevice>();
+
+        async move {
+            let info = device.lost().await;
+            log(&format!("device lost: {}", GpuError::from(info)));
+        }
+    });
+    device
+        .on_uncaptured_error(|e| log(&format!("uncaptured gpu error: {e}")))
+        .forget();
+    let format = gpu
+        .get_preferred_canvas_format()
+        .expect("Failed to get preferred canvas format");
 
     let buffer = device
         .create_buffer(GPUBufferCreateDescription {
             label: None,
             mapped_at_creation: None,
             size: 128,
-            usage: *VERTEX | *COPY_DST,
+            usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
         })
         .expect("Failed to create buffer");
-    let staging_buffer = device
-        .create_buffer(GPUBufferCreateDescription {
-            label: None,
-            mapped_at_creation: Some(true),
-            size: buffer.size() as _,
-            usage: *COPY_SRC | *MAP_WRITE,
-        })
-        .expect("Failed to create staging buffer");
-    let mapped_ab = staging_buffer.get_mapped_range_full().expect("not mapped?");
-    Float32Array::new_with_byte_offset_and_length(&mapped_ab, 0, 6)
-        .copy_from(&[0.0, -0.5, 0.75, 0.5, -0.75, 0.5]);
-    staging_buffer.unmap();
-
-    let copy_cmd = device
-        .create_command_encoder()
-        .expect("Failed to create command encoder");
-    copy_cmd
-        .copy_buffer_to_buffer(&staging_buffer, 0, &buffer, 0, 4 * 6)
-        .expect("Failed to insert copy command");
-    let copy_cmd = copy_cmd
-        .finish()
-        .expect("Failed to finish copy cmd recording");
-    device
-        .queue()
-        .submit(vec![copy_cmd])
-        .expect("Failed to send copy commands");
 
     let shader = device
         .create_shader_module(
@@ -721,35 +4677,56 @@ pub async fn start(render_target_element: &HTMLCanvasElement) {
                 .expect("Failed to create pipeline layout descriptor"),
         )
         .expect("Failed to create pipeline layout");
-    let render_pipeline = device
-        .create_render_pipeline(
-            GPURenderPipelineDescriptor::new(
-                &empty_pl,
-                GPURenderPipelineVertexProperties {
-                    entry_point: "vsh".into(),
-                    module: &shader,
-                    buffers: Some(vec![GPUVertexBufferLayout {
-                        array_stride: 4 * 2,
-                        attributes: vec![GPUVertexAttribute {
-                            format: "float32x2".into(),
-                            offset: 0,
-                            shader_location: 0,
-                        }],
-                    }]),
-                },
-            )
-            .fragment(GPURenderPipelineFragmentProperties {
-                entry_point: "fsh".into(),
-                module: &shader,
-                targets: vec![GPURenderPipelineFragmentTarget {
-                    format: format.clone(),
+    let surface = create_surface(
+        &device,
+        &ctx,
+        SurfaceDescriptor {
+            format,
+            sample_count: 4,
+            depth_format: None,
+        },
+    )
+    .expect("Failed to create surface");
+    surface.configure(
+        GPUCanvasConfiguration::new(&device)?
+            .with_format(format)?
+            .alpha_mode(GPUCanvasAlphaMode::Opaque)?,
+    )?;
+    log(&format!("canvas was configured with format {format:?}"));
+    surface.on_resize(|w, h| log(&format!("surface resized to {w}x{h}")));
+    let render_pipeline_descriptor = GPURenderPipelineDescriptor::new(
+        &empty_pl,
+        GPURenderPipelineVertexProperties {
+            entry_point: "vsh".into(),
+            module: &shader,
+            buffers: Some(vec![GPUVertexBufferLayout {
+                array_stride: 4 * 2,
+                attributes: vec![GPUVertexAttribute {
+                    format: GPUVertexFormat::Float32x2,
+                    offset: 0,
+                    shader_location: 0,
                 }],
-            }),
-        )
+            }]),
+        },
+    )
+    .and_then(|d| {
+        d.fragment(GPURenderPipelineFragmentProperties {
+            entry_point: "fsh".into(),
+            module: &shader,
+            targets: vec![GPURenderPipelineFragmentTarget::new(format)],
+        })
+    })
+    .and_then(|d| d.multisample(surface.multisample_state()))
+    .expect("Failed to create render pipeline descriptor");
+    let render_pipeline = device
+        .create_render_pipeline(render_pipeline_descriptor)
         .expect("Failed to create render pipeline");
 
+    let triangle_render_descriptor = GPURenderBundleEncoderDescriptor::new(vec![format])
+        .and_then(|d| d.sample_count(surface.sample_count()))
+        .expect("Failed to create triangle render bundle encoder descriptor");
     let triangle_render = device
-        .create_render_bundle_encoder(GPURenderBundleEncoderDescriptor::new(vec![format]))
+        .create_render_bundle_encoder(triangle_render_descriptor)
         .expect("Failed to create triangle render bundle encoder");
     triangle_render
         .set_pipeline(&render_pipeline)
@@ -762,31 +4739,272 @@ pub async fn start(render_target_element: &HTMLCanvasElement) {
         .finish()
         .expect("Failed to finish triangle render bundle");
 
-    let render_target_view = ctx
-        .get_current_texture()
-        .expect("no current texture")
-        .create_view()
-        .expect("Failed to create target view");
-    let main_target_attachment = GPURenderPassColorAttachment::new(&render_target_view)
-        .clear_by_array(&[0.0, 0.0, 0.0, 1.0]);
-    let render_pass = GPURenderPassDescriptor {
-        color_attachments: vec![main_target_attachment],
-    };
+    let handle = surface.clone();
+    let frame_loop = FrameLoop::new(ctx, move |frame| {
+        let (sin, cos) = ((frame.timestamp / 1000.0) as f32).sin_cos();
+        let vertices = [[0.0f32, -0.5], [0.75, 0.5], [-0.75, 0.5]]
+            .map(|[x, y]| [x * cos - y * sin, x * sin + y * cos]);
 
-    let render_commands = device
-        .create_command_encoder()
-        .expect("Failed to begin render command recording");
-    let rp = render_commands
-        .begin_render_pass(render_pass)
-        .expect("Failed to begin render pass");
-    rp.execute_bundles(vec![triangle_render])
-        .expect("Failed to execute render bundles");
-    rp.end().expect("Failed to finish render pass");
-    let render_commands = render_commands
-        .finish()
-        .expect("Failed to finish render commands");
-    device
-        .queue()
-        .submit(vec![render_commands])
-        .expect("Failed to submit render commands");
+        device.push_error_scope(GPUErrorFilter::Validation);
+        let result = (|| {
+            device.queue().write_buffer_data(&buffer, 0, &vertices)?;
+
+            let render_target_view = frame.texture.create_view()?;
+            let render_pass = GPURenderPassDescriptor {
+                color_attachments: vec![surface
+                    .color_attachment(&render_target_view)?
+                    .clear(GPUColor::BLACK)?],
+                depth_stencil_attachment: surface.depth_stencil_attachment()?,
+            };
+
+            let render_commands = device.create_command_encoder()?;
+            let rp = render_commands.begin_render_pass(render_pass)?;
+            rp.execute_bundles(vec![JsValue::clone(&triangle_render).unchecked_into()])?;
+            rp.end()?;
+            device.queue().submit(vec![render_commands.finish()?])
+        })();
+
+        let device = JsValue::clone(&device).unchecked_into::<GPUDevice>();
+        wasm_bindgen_futures::spawn_local(async move {
+            match device.pop_error_scope().await {
+                Ok(None) => (),
+                Ok(Some(e)) => log(&format!("frame produced an error: {e}")),
+                Err(e) => log(&format!("Failed to pop error scope: {e}")),
+            }
+        });
+
+        result
+    });
+    frame_loop.start();
+    // keep rendering for the lifetime of the page
+    std::mem::forget(frame_loop);
+
+    Ok(handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_match_spec_bits() {
+        assert_eq!(BufferUsages::MAP_READ.bits(), 0x0001);
+        assert_eq!(BufferUsages::COPY_DST.bits(), 0x0008);
+        assert_eq!(BufferUsages::VERTEX.bits(), 0x0020);
+        assert_eq!(BufferUsages::QUERY_RESOLVE.bits(), 0x0200);
+        assert_eq!(TextureUsages::TEXTURE_BINDING.bits(), 0x04);
+        assert_eq!(TextureUsages::RENDER_ATTACHMENT.bits(), 0x10);
+        assert_eq!(ShaderStages::COMPUTE.bits(), 0x4);
+        assert_eq!(MapMode::WRITE.bits(), 0x2);
+        assert_eq!(ColorWrites::ALPHA.bits(), 0x8);
+    }
+
+    #[test]
+    fn flags_convert_to_u32() {
+        assert_eq!(
+            u32::from(BufferUsages::VERTEX | BufferUsages::COPY_DST),
+            0x28
+        );
+        assert_eq!(
+            u32::from(ShaderStages::VERTEX | ShaderStages::FRAGMENT),
+            0x3
+        );
+    }
+
+    #[test]
+    fn color_writes_default_to_all() {
+        assert_eq!(ColorWrites::default(), ColorWrites::ALL);
+        assert_eq!(
+            ColorWrites::ALL,
+            ColorWrites::RED | ColorWrites::GREEN | ColorWrites::BLUE | ColorWrites::ALPHA
+        );
+    }
+
+    #[test]
+    fn texture_format_names_round_trip() {
+        for (name, format) in [
+            (
+                "depth32float-stencil8",
+                GPUTextureFormat::Depth32FloatStencil8,
+            ),
+            (
+                "astc-10x10-unorm-srgb",
+                GPUTextureFormat::Astc10x10UnormSrgb,
+            ),
+            ("rg11b10ufloat", GPUTextureFormat::Rg11b10Ufloat),
+            ("bc1-rgba-unorm", GPUTextureFormat::Bc1RgbaUnorm),
+            ("rgba8unorm-srgb", GPUTextureFormat::Rgba8UnormSrgb),
+        ] {
+            assert_eq!(name.parse::<GPUTextureFormat>().unwrap(), format);
+            assert_eq!(
+                serde_json::to_string(&format).unwrap(),
+                format!("\"{name}\"")
+            );
+        }
+
+        assert!("rgba8unorm-linear".parse::<GPUTextureFormat>().is_err());
+    }
+
+    #[test]
+    fn texture_format_block_metadata() {
+        let f = GPUTextureFormat::Rgba8Unorm;
+        assert_eq!(f.block_dimensions(), (1, 1));
+        assert_eq!(f.block_copy_size(), Some(4));
+        assert!(!f.is_compressed());
+        assert_eq!(f.bytes_per_row(100), Some(400));
+
+        let f = GPUTextureFormat::Bc1RgbaUnorm;
+        assert_eq!(f.block_dimensions(), (4, 4));
+        assert_eq!(f.block_copy_size(), Some(8));
+        assert!(f.is_compressed());
+        assert_eq!(
+            f.required_feature(),
+            Some(GPUFeatureName::TextureCompressionBc)
+        );
+        // 10 texels wide rounds up to 3 blocks
+        assert_eq!(f.bytes_per_row(10), Some(24));
+
+        assert_eq!(
+            GPUTextureFormat::Astc10x10UnormSrgb.block_dimensions(),
+            (10, 10)
+        );
+        assert_eq!(GPUTextureFormat::Depth24Plus.block_copy_size(), None);
+    }
+
+    #[test]
+    fn aligned_bytes_per_row_rounds_up() {
+        let f = GPUTextureFormat::Rgba8Unorm;
+        assert_eq!(
+            f.aligned_bytes_per_row(1),
+            Some(COPY_BYTES_PER_ROW_ALIGNMENT)
+        );
+        assert_eq!(f.aligned_bytes_per_row(64), Some(256));
+        assert_eq!(f.aligned_bytes_per_row(65), Some(512));
+        assert_eq!(
+            GPUTextureFormat::Depth24Plus.aligned_bytes_per_row(64),
+            None
+        );
+    }
+
+    #[test]
+    fn limits_intersection_takes_weaker_values() {
+        let a = Limits {
+            max_bind_groups: 8,
+            max_buffer_size: 1 << 30,
+            min_uniform_buffer_offset_alignment: 64,
+            ..Default::default()
+        };
+        let b = Limits {
+            max_bind_groups: 4,
+            max_buffer_size: 1 << 32,
+            min_uniform_buffer_offset_alignment: 256,
+            ..Default::default()
+        };
+
+        let i = a.intersection(&b);
+        assert_eq!(i.max_bind_groups, 4);
+        assert_eq!(i.max_buffer_size, 1 << 30);
+        assert_eq!(i.min_uniform_buffer_offset_alignment, 256);
+        assert_eq!(i, b.intersection(&a));
+    }
+
+    #[test]
+    fn alignment_limits_are_satisfied_by_larger_values() {
+        assert!(limit_satisfied("maxBindGroups", 4, 8));
+        assert!(!limit_satisfied("maxBindGroups", 16, 8));
+        // for min* limits a smaller value is the stronger requirement
+        assert!(limit_satisfied("minUniformBufferOffsetAlignment", 256, 64));
+        assert!(!limit_satisfied("minUniformBufferOffsetAlignment", 32, 64));
+    }
+
+    #[test]
+    fn limits_requirements() {
+        let supported = Limits {
+            max_storage_buffer_binding_size: 1 << 30,
+            min_storage_buffer_offset_alignment: 32,
+            ..Default::default()
+        };
+
+        assert!(supported.meets_requirements(&Limits::default()));
+        assert!(supported.meets_requirements(&Limits {
+            max_storage_buffer_binding_size: 1 << 29,
+            min_storage_buffer_offset_alignment: 64,
+            ..Default::default()
+        }));
+        assert_eq!(
+            supported.check_requirements(&Limits {
+                max_storage_buffer_binding_size: 1 << 31,
+                ..Default::default()
+            }),
+            Err(GpuError::UnsupportedLimit {
+                name: "maxStorageBufferBindingSize".into(),
+                requested: 1 << 31,
+                supported: 1 << 30,
+            })
+        );
+
+        let required = Limits {
+            max_bind_groups: 8,
+            ..Default::default()
+        };
+        assert_eq!(required.non_default_entries(), vec![("maxBindGroups", 8)]);
+        assert_eq!(required.get("maxBindGroups"), Some(8));
+        assert_eq!(required.get("maxUnknown"), None);
+    }
+
+    #[test]
+    fn feature_requirements() {
+        let supported: Features = [
+            GPUFeatureName::TimestampQuery,
+            GPUFeatureName::TextureCompressionBc,
+            GPUFeatureName::ShaderF16,
+        ]
+        .into_iter()
+        .collect();
+        let required: Features = [GPUFeatureName::TimestampQuery, GPUFeatureName::ShaderF16]
+            .into_iter()
+            .collect();
+
+        assert!(supported.contains(GPUFeatureName::TextureCompressionBc));
+        assert!(!supported.contains(GPUFeatureName::Subgroups));
+        assert!(supported.meets_requirements(&required));
+        assert!(!required.meets_requirements(&supported));
+        assert_eq!(supported.check_requirements(&required), Ok(()));
+        assert_eq!(
+            required.check_requirements(&supported),
+            Err(GpuError::UnsupportedFeature(
+                GPUFeatureName::TextureCompressionBc
+            ))
+        );
+        assert_eq!(supported.intersection(&required), required);
+        assert!(Features::default().meets_requirements(&Features::default()));
+    }
+
+    #[test]
+    fn feature_names_round_trip() {
+        for (name, feature) in [
+            ("timestamp-query", GPUFeatureName::TimestampQuery),
+            (
+                "depth32float-stencil8",
+                GPUFeatureName::Depth32floatStencil8,
+            ),
+            (
+                "texture-compression-bc-sliced-3d",
+                GPUFeatureName::TextureCompressionBcSliced3d,
+            ),
+        ] {
+            assert_eq!(name.parse::<GPUFeatureName>().unwrap(), feature);
+            assert_eq!(
+                serde_json::to_string(&feature).unwrap(),
+                format!("\"{name}\"")
+            );
+        }
+    }
+
+    #[test]
+    fn surface_size_is_clamped_to_texture_limits() {
+        assert_eq!(SurfaceTargets::clamp_size(0, 0, 8192), (1, 1));
+        assert_eq!(SurfaceTargets::clamp_size(640, 480, 8192), (640, 480));
+        assert_eq!(SurfaceTargets::clamp_size(10000, 0, 8192), (8192, 1));
+    }
 }
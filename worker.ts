@@ -0,0 +1,18 @@
+import init, { start_offscreen, SurfaceHandle } from "./pkg/wasm_webgpu_test";
+
+const ready = init();
+let surface: SurfaceHandle | undefined;
+let pendingSize: { width: number; height: number } | undefined;
+
+self.onmessage = async ({ data }: MessageEvent) => {
+    await ready;
+
+    if (data.canvas) {
+        surface = await start_offscreen(data.canvas);
+        if (pendingSize) surface.resize(pendingSize.width, pendingSize.height);
+    } else if (surface) {
+        surface.resize(data.width, data.height);
+    } else {
+        pendingSize = data;
+    }
+};